@@ -2,6 +2,7 @@
 //!
 //! Usage:
 //!   cargo run --release --example equity_bench
+//!   cargo run --release --features parallel --example equity_bench
 //!
 //! This measures:
 //! - Heads-up equity (2 players)
@@ -12,13 +13,21 @@
 use std::time::Instant;
 use poker_eval::{
     equity_exact_vs_hand_checked,
+    equity_exact_vs_random_checked,
     equity_mc_vs_hand_checked,
     equity_mc_vs_random_checked,
     equity_mc_multiway_checked,
     equity_mc_vs_random_multiway_checked,
     equity_exact_multiway_checked,
-    Card, Rank::*, Suit::*,
+    equity_mc_vs_hand_sampled_checked,
+    equity_histogram,
+    equity_histograms,
+    hand_strength_vs_random,
+    river_showdown,
+    Card, NutRanking, Range, Rank::*, Suit::*, Sampling,
 };
+#[cfg(feature = "parallel")]
+use poker_eval::{equity_exact_vs_hand_checked_par, equity_mc_vs_hand_checked_par};
 
 fn format_duration(nanos: u128) -> String {
     if nanos < 1_000 {
@@ -58,6 +67,21 @@ where
     );
 }
 
+/// RMSE against `exact` over `runs` seeds, and mean time per run.
+fn sampling_accuracy<F>(exact: f64, runs: u64, mut f: F) -> (f64, f64)
+where
+    F: FnMut(u64) -> f64,
+{
+    let start = Instant::now();
+    let mut sq = 0.0;
+    for seed in 1..=runs {
+        let e = f(seed) - exact;
+        sq += e * e;
+    }
+    let secs = start.elapsed().as_secs_f64() / runs as f64;
+    ((sq / runs as f64).sqrt(), secs)
+}
+
 fn main() {
     println!("=== Equity Calculator Benchmarks ===\n");
     println!("{:50} {:>12}  {:>13}", "Scenario", "Time/Iter", "Throughput");
@@ -131,6 +155,62 @@ fn main() {
         let _ = equity_mc_vs_random_checked(&aces, &flop, 10_000, 42).unwrap();
     });
 
+    bench("HU vs Random Exact: Turn", 100, || {
+        let _ = equity_exact_vs_random_checked(&aces, &turn).unwrap();
+    });
+
+    bench("HU vs Random Exact: Flop", 10, || {
+        let _ = equity_exact_vs_random_checked(&aces, &flop).unwrap();
+    });
+
+    bench("HU vs Random Exact: Preflop", 1, || {
+        let _ = equity_exact_vs_random_checked(&aces, &[]).unwrap();
+    });
+
+    println!("\n--- River Range vs Range (sort and sweep) ---");
+
+    let all = Range::random();
+    let board_river = [
+        Card::new(Clubs, King).id(),
+        Card::new(Diamonds, Queen).id(),
+        Card::new(Hearts, Two).id(),
+        Card::new(Spades, Three).id(),
+        Card::new(Clubs, Four).id(),
+    ];
+    bench("1326 vs 1326 combos: per-combo showdown", 100, || {
+        let _ = river_showdown(&all, &all, &board_river).unwrap();
+    });
+
+    println!("\n--- Hand Strength (HS, PPot/NPot, EHS, EHS²) ---");
+
+    bench("vs Random: Turn", 100, || {
+        let _ = hand_strength_vs_random(&aces, &turn, &[]).unwrap();
+    });
+
+    bench("vs Random: Flop", 10, || {
+        let _ = hand_strength_vs_random(&aces, &flop, &[]).unwrap();
+    });
+
+    println!("\n--- Equity Histograms (river equity over runouts) ---");
+
+    bench("One hand: Flop", 10, || {
+        let _ = equity_histogram(&aces, &flop, &[], 50).unwrap();
+    });
+
+    bench("All 1326 combos: Flop", 3, || {
+        let _ = equity_histograms(&all, &all, &flop, &[], 50).unwrap();
+    });
+
+    println!("\n--- Nut Ranking (all holdings, nut chances over rivers) ---");
+
+    bench("Turn", 10, || {
+        let _ = NutRanking::new(&turn, &[]).unwrap();
+    });
+
+    bench("Flop", 3, || {
+        let _ = NutRanking::new(&flop, &[]).unwrap();
+    });
+
     println!("\n--- Multi-Way Exact Equity ---");
 
     bench("3-way Exact: Turn", 100, || {
@@ -195,6 +275,50 @@ fn main() {
         let _ = equity_mc_vs_random_multiway_checked(&aces, 8, &[], 10_000, 42).unwrap();
     });
 
+    #[cfg(feature = "parallel")]
+    {
+        println!("\n--- Parallel (rayon) ---");
+
+        bench("HU Exact par: Preflop", 10, || {
+            let _ = equity_exact_vs_hand_checked_par(&aces, &kings, &[], &[]).unwrap();
+        });
+
+        bench("HU MC par 1M (64 shards): Preflop", 10, || {
+            let _ = equity_mc_vs_hand_checked_par(&aces, &kings, &[], &[], 1_000_000, 42, 64).unwrap();
+        });
+    }
+
+    println!("\n--- Sampling Strategies (HU, 1k iterations, RMSE vs exact over 200 seeds) ---");
+    println!("{:30} {:>10} {:>12} {:>14}", "Strategy", "RMSE", "Time/Run", "Eff. vs Unif.");
+
+    for (street, board) in [("Preflop", &[][..]), ("Flop", &flop[..]), ("Turn", &turn[..])] {
+        let exact = equity_exact_vs_hand_checked(&aces, &kings, board).unwrap().equity();
+        let mut base = 0.0;
+        for kind in [
+            Sampling::Uniform,
+            Sampling::Stratified,
+            Sampling::Systematic,
+            Sampling::QuasiRandom,
+        ] {
+            let (rmse, secs) = sampling_accuracy(exact, 200, |seed| {
+                equity_mc_vs_hand_sampled_checked(&aces, &kings, board, &[], 1_000, seed, kind)
+                    .unwrap()
+                    .equity()
+            });
+            // Efficiency = 1 / (error^2 * time): how much more accuracy per unit of time.
+            let eff = 1.0 / (rmse * rmse * secs);
+            if kind == Sampling::Uniform {
+                base = eff;
+            }
+            println!("{:30} {:>10.5} {:>12} {:>13.1}x",
+                format!("{}: {:?}", street, kind),
+                rmse,
+                format_duration((secs * 1e9) as u128),
+                eff / base
+            );
+        }
+    }
+
     println!("\n--- MC Iteration Scaling (Preflop HU) ---");
 
     bench("MC 1k iterations", 1000, || {
@@ -217,14 +341,14 @@ fn main() {
     println!("\n=== Recommendations ===\n");
     println!("For known hands:");
     println!("  • River/Turn: Always use EXACT (instant)");
-    println!("  • Flop: Use EXACT if < 4 players (< 100ms)");
-    println!("  • Preflop: Use EXACT if heads-up (< 100ms), else MC with 10k-100k iterations");
+    println!("  • Flop: Use EXACT (< 1ms)");
+    println!("  • Preflop: Use EXACT up to 3 players (~10ms), else MC with 10k-100k iterations");
     println!("\nFor random opponents:");
     println!("  • Always use Monte Carlo");
     println!("  • 10k iterations: Good for real-time (< 100ms)");
     println!("  • 100k iterations: High accuracy (< 1s)");
     println!("  • 1M iterations: Research-grade accuracy");
-    println!("\nNote: Exact equity preflop with 3+ players can take 100ms-1s");
+    println!("\nNote: Exact equity preflop with 4+ players can take 10-100ms");
     println!("      (still very fast, but use MC for real-time applications)");
     println!();
 }
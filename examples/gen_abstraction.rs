@@ -0,0 +1,38 @@
+//! Cluster one street's (hole, board) situations into buckets and save the map.
+//!
+//! Usage:
+//!   cargo run --release --example gen_abstraction -- <board cards 3-5> <buckets> [<path>]
+//!
+//! Writes `data/buckets_<board cards>_<buckets>.bin` by default. The flop takes
+//! several minutes; the river needs a lot of memory.
+
+use std::time::Instant;
+
+use poker_eval::{AbstractionConfig, BucketMap};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 2 {
+        eprintln!("usage: gen_abstraction <board cards 3-5> <buckets> [<path>]");
+        std::process::exit(2);
+    }
+    let board_cards: usize = args[0].parse().expect("board cards");
+    let buckets: usize = args[1].parse().expect("buckets");
+    let path = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| format!("data/buckets_{}_{}.bin", board_cards, buckets));
+
+    let start = Instant::now();
+    let map = BucketMap::build(board_cards, &AbstractionConfig::new(buckets)).expect("build buckets");
+    let bytes = map.to_bytes();
+    std::fs::write(&path, &bytes).expect("write map");
+    println!(
+        "{} boards, {} buckets, {} bytes -> {} ({:.1}s)",
+        map.num_boards(),
+        map.num_buckets(),
+        bytes.len(),
+        path,
+        start.elapsed().as_secs_f64()
+    );
+}
@@ -0,0 +1,50 @@
+//! Regenerate the embedded preflop equity tables.
+//!
+//! Usage:
+//!   cargo run --release --example gen_preflop_table [-- hu|random [<path>]]
+//!   cargo run --release --features parallel --example gen_preflop_table
+//!
+//! `hu` writes `data/preflop_hu.bin` (the default); `random` writes
+//! `data/preflop_vs_random.bin`, which reads the heads-up table, so regenerate
+//! that one first.
+
+use std::time::Instant;
+
+use poker_eval::{HandClass, PreflopTable, PreflopVsRandomTable, MAX_RANDOM_OPPONENTS};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let which = args.next().unwrap_or_else(|| "hu".to_string());
+
+    let start = Instant::now();
+    let (path, entries, bytes) = match which.as_str() {
+        "hu" => {
+            #[cfg(feature = "parallel")]
+            let table = PreflopTable::generate_par();
+            #[cfg(not(feature = "parallel"))]
+            let table = PreflopTable::generate();
+            ("data/preflop_hu.bin", table.len(), table.to_bytes())
+        }
+        "random" => {
+            #[cfg(feature = "parallel")]
+            let table = PreflopVsRandomTable::generate_par();
+            #[cfg(not(feature = "parallel"))]
+            let table = PreflopVsRandomTable::generate();
+            ("data/preflop_vs_random.bin", HandClass::COUNT * MAX_RANDOM_OPPONENTS, table.to_bytes())
+        }
+        other => {
+            eprintln!("unknown table '{}' (expected 'hu' or 'random')", other);
+            std::process::exit(2);
+        }
+    };
+    let path = args.next().unwrap_or_else(|| path.to_string());
+
+    std::fs::write(&path, &bytes).expect("write table");
+    println!(
+        "{} entries, {} bytes -> {} ({:.1}s)",
+        entries,
+        bytes.len(),
+        path,
+        start.elapsed().as_secs_f64()
+    );
+}
@@ -0,0 +1,530 @@
+//! Card abstraction: cluster the (hole, board) situations of a street into buckets.
+//!
+//! Situations that differ only by a suit permutation are the same point. Each point
+//! is described by its equity histogram over the runouts to the river (see
+//! [`equity_histograms`]) and weighted by how many concrete (hole, board) pairs it
+//! stands for. Weighted k-means then groups the points, measuring distance with the
+//! earth mover's distance between histograms; centroids are the weighted mean
+//! histograms. Buckets are numbered by mean equity, 0 being the weakest.
+//!
+//! Building a whole street is an offline job: the flop has 1,755 canonical boards
+//! and about 1.3M points, and every k-means pass costs points × buckets × bins.
+//!
+//! The serialized form is a 12-byte header (`b"ABKT"`, version, board size, bucket
+//! count as u16, board count as u32), the canonical board masks as u64, then one u16
+//! bucket per board and holding (`u16::MAX` where the holding touches the board),
+//! all little-endian.
+
+use std::collections::HashMap;
+
+use crate::equity::{EquityError, XorShift64};
+use crate::histogram::equity_histograms;
+use crate::range::Range;
+
+const MAGIC: &[u8; 4] = b"ABKT";
+const VERSION: u8 = 1;
+const HEADER: usize = 12;
+const HOLDINGS: usize = 1326;
+const NO_BUCKET: u16 = u16::MAX;
+
+/// Parameters of the clustering.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AbstractionConfig {
+    /// Number of buckets (k).
+    pub buckets: usize,
+    /// Histogram bins over [0,1].
+    pub bins: usize,
+    /// Cap on k-means passes; it also stops when no point changes bucket.
+    pub max_iters: usize,
+    /// Seed for the k-means++ initialization.
+    pub seed: u64,
+}
+
+impl AbstractionConfig {
+    /// `buckets` buckets over 30-bin histograms, at most 50 passes.
+    pub fn new(buckets: usize) -> Self {
+        Self {
+            buckets,
+            bins: 30,
+            max_iters: 50,
+            seed: 1,
+        }
+    }
+}
+
+/// The 24 suit permutations.
+const fn build_perms() -> [[u8; 4]; 24] {
+    let mut out = [[0u8; 4]; 24];
+    let mut n = 0;
+    let mut code = 0;
+    while code < 256 {
+        let p = [(code >> 6) as u8, ((code >> 4) & 3) as u8, ((code >> 2) & 3) as u8, (code & 3) as u8];
+        if p[0] != p[1] && p[0] != p[2] && p[0] != p[3] && p[1] != p[2] && p[1] != p[3] && p[2] != p[3] {
+            out[n] = p;
+            n += 1;
+        }
+        code += 1;
+    }
+    out
+}
+
+const PERMS: [[u8; 4]; 24] = build_perms();
+
+/// Card mask with suit `s` relabeled `perm[s]`.
+#[inline(always)]
+fn permute(mask: u64, perm: &[u8; 4]) -> u64 {
+    let mut out = 0u64;
+    for (s, &p) in perm.iter().enumerate() {
+        out |= ((mask >> (13 * s)) & 0x1FFF) << (13 * p as usize);
+    }
+    out
+}
+
+/// Smallest image of a board mask and a permutation producing it.
+fn canonical_board(mask: u64) -> (u64, &'static [u8; 4]) {
+    PERMS
+        .iter()
+        .map(|p| (permute(mask, p), p))
+        .min_by_key(|&(m, _)| m)
+        .unwrap()
+}
+
+/// Number of distinct boards a suit permutation maps `mask` to.
+fn orbit_size(mask: u64) -> u64 {
+    let mut images: Vec<u64> = PERMS.iter().map(|p| permute(mask, p)).collect();
+    images.sort_unstable();
+    images.dedup();
+    images.len() as u64
+}
+
+/// Index of the holding `a < b` among the 1,326.
+#[inline(always)]
+fn pair_index(a: u8, b: u8) -> usize {
+    let (a, b) = (a as usize, b as usize);
+    a * 51 - a * (a.saturating_sub(1)) / 2 + (b - a - 1)
+}
+
+fn mask_cards(mut mask: u64) -> Vec<u8> {
+    let mut cards = Vec::with_capacity(mask.count_ones() as usize);
+    while mask != 0 {
+        cards.push(mask.trailing_zeros() as u8);
+        mask &= mask - 1;
+    }
+    cards
+}
+
+/// Canonical masks of every board with `n` cards, ascending.
+fn canonical_boards(n: usize) -> Vec<u64> {
+    fn walk(start: u8, left: usize, mask: u64, out: &mut Vec<u64>) {
+        if left == 0 {
+            if canonical_board(mask).0 == mask {
+                out.push(mask);
+            }
+            return;
+        }
+        for c in start..=(52 - left as u8) {
+            walk(c + 1, left - 1, mask | (1u64 << c), out);
+        }
+    }
+    let mut out = Vec::new();
+    walk(0, n, 0, &mut out);
+    out.sort_unstable();
+    out
+}
+
+/// Earth mover's distance between two histograms given as CDFs over equal bins.
+#[inline(always)]
+fn emd(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum::<f32>() / a.len() as f32
+}
+
+/// Buckets of every holding on every canonical board of one street.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BucketMap {
+    board_cards: u8,
+    buckets: u16,
+    /// Canonical board masks, ascending.
+    boards: Vec<u64>,
+    /// Bucket per (board, holding).
+    table: Vec<u16>,
+}
+
+impl BucketMap {
+    /// Cluster every situation of the street with `board_cards` (3-5) board cards.
+    pub fn build(board_cards: usize, cfg: &AbstractionConfig) -> Result<Self, EquityError> {
+        check_board_size(board_cards)?;
+        check_config(cfg)?;
+        Self::cluster(board_cards, canonical_boards(board_cards), cfg)
+    }
+
+    /// Like [`build`](Self::build), restricted to the given boards (and the boards
+    /// isomorphic to them); other boards have no bucket.
+    pub fn build_on_boards(board_cards: usize, boards: &[&[u8]], cfg: &AbstractionConfig) -> Result<Self, EquityError> {
+        check_board_size(board_cards)?;
+        check_config(cfg)?;
+        let mut masks = Vec::with_capacity(boards.len());
+        for board in boards {
+            masks.push(canonical_board(board_mask(board, board_cards)?).0);
+        }
+        masks.sort_unstable();
+        masks.dedup();
+        Self::cluster(board_cards, masks, cfg)
+    }
+
+    fn cluster(board_cards: usize, boards: Vec<u64>, cfg: &AbstractionConfig) -> Result<Self, EquityError> {
+        let bins = cfg.bins;
+        let all = Range::random();
+        let mut cdfs: Vec<f32> = Vec::new();
+        let mut means: Vec<f64> = Vec::new();
+        let mut weights: Vec<f64> = Vec::new();
+        let mut slot = vec![u32::MAX; boards.len() * HOLDINGS];
+
+        for (bi, &board) in boards.iter().enumerate() {
+            let stabilizer: Vec<&[u8; 4]> = PERMS.iter().filter(|p| permute(board, p) == board).collect();
+            let orbit = orbit_size(board) as f64;
+            let hists = equity_histograms(&all, &all, &mask_cards(board), &[], bins)?;
+            let mut seen: HashMap<u64, u32> = HashMap::new();
+            for h in &hists {
+                let hole = (1u64 << h.hand[0]) | (1u64 << h.hand[1]);
+                let canon = stabilizer.iter().map(|p| permute(hole, p)).min().unwrap();
+                let point = *seen.entry(canon).or_insert_with(|| {
+                    let mut acc = 0.0f32;
+                    cdfs.extend(h.bins.iter().map(|&p| {
+                        acc += p as f32;
+                        acc
+                    }));
+                    means.push(h.mean);
+                    weights.push(0.0);
+                    (weights.len() - 1) as u32
+                });
+                weights[point as usize] += orbit;
+                slot[bi * HOLDINGS + pair_index(h.hand[0], h.hand[1])] = point;
+            }
+        }
+
+        let assign = kmeans(&cdfs, &weights, bins, cfg);
+        let k = cfg.buckets.min(weights.len());
+
+        // Renumber clusters by weighted mean equity, weakest first.
+        let mut sums = vec![(0.0f64, 0.0f64); k];
+        for ((&a, &w), &m) in assign.iter().zip(&weights).zip(&means) {
+            sums[a as usize].0 += w * m;
+            sums[a as usize].1 += w;
+        }
+        let mut order: Vec<usize> = (0..k).collect();
+        order.sort_by(|&a, &b| {
+            let mean = |i: usize| sums[i].0 / sums[i].1.max(f64::MIN_POSITIVE);
+            mean(a).total_cmp(&mean(b))
+        });
+        let mut label = vec![0u16; k];
+        for (new, &old) in order.iter().enumerate() {
+            label[old] = new as u16;
+        }
+
+        let table = slot
+            .iter()
+            .map(|&p| if p == u32::MAX { NO_BUCKET } else { label[assign[p as usize] as usize] })
+            .collect();
+        Ok(Self {
+            board_cards: board_cards as u8,
+            buckets: cfg.buckets as u16,
+            boards,
+            table,
+        })
+    }
+
+    /// Board size of the street this map covers.
+    #[inline(always)]
+    pub fn board_cards(&self) -> usize {
+        self.board_cards as usize
+    }
+
+    #[inline(always)]
+    pub fn num_buckets(&self) -> usize {
+        self.buckets as usize
+    }
+
+    /// Number of canonical boards covered.
+    #[inline(always)]
+    pub fn num_boards(&self) -> usize {
+        self.boards.len()
+    }
+
+    /// Bucket of `hole` on `board`; `None` if the cards are invalid or overlap, the
+    /// board has the wrong size, or it is not covered by the map.
+    pub fn bucket(&self, hole: &[u8; 2], board: &[u8]) -> Option<u16> {
+        let mask = board_mask(board, self.board_cards as usize).ok()?;
+        if hole[0] >= 52 || hole[1] >= 52 || hole[0] == hole[1] {
+            return None;
+        }
+        if mask & ((1u64 << hole[0]) | (1u64 << hole[1])) != 0 {
+            return None;
+        }
+        let (canon, perm) = canonical_board(mask);
+        let bi = self.boards.binary_search(&canon).ok()?;
+        let map = |c: u8| perm[(c / 13) as usize] * 13 + c % 13;
+        let (a, b) = (map(hole[0]), map(hole[1]));
+        let b = self.table[bi * HOLDINGS + pair_index(a.min(b), a.max(b))];
+        (b != NO_BUCKET).then_some(b)
+    }
+
+    /// Serialize to the format described in the module docs.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER + 8 * self.boards.len() + 2 * self.table.len());
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.push(self.board_cards);
+        out.extend_from_slice(&self.buckets.to_le_bytes());
+        out.extend_from_slice(&(self.boards.len() as u32).to_le_bytes());
+        for &b in &self.boards {
+            out.extend_from_slice(&b.to_le_bytes());
+        }
+        for &b in &self.table {
+            out.extend_from_slice(&b.to_le_bytes());
+        }
+        out
+    }
+
+    /// Load a map written by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EquityError> {
+        if bytes.len() < HEADER || &bytes[..4] != MAGIC || bytes[4] != VERSION {
+            return Err(EquityError::InvalidTable);
+        }
+        let board_cards = bytes[5];
+        let buckets = u16::from_le_bytes([bytes[6], bytes[7]]);
+        let n = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize;
+        if check_board_size(board_cards as usize).is_err()
+            || buckets == 0
+            || buckets == NO_BUCKET
+            || bytes.len() != HEADER + n * (8 + 2 * HOLDINGS)
+        {
+            return Err(EquityError::InvalidTable);
+        }
+        let (board_bytes, table_bytes) = bytes[HEADER..].split_at(8 * n);
+        let boards: Vec<u64> = board_bytes
+            .chunks_exact(8)
+            .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        let canonical = |&m: &u64| m >> 52 == 0 && m.count_ones() == board_cards as u32 && canonical_board(m).0 == m;
+        if !boards.iter().all(canonical) || boards.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(EquityError::InvalidTable);
+        }
+        let table: Vec<u16> = table_bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        if table.iter().any(|&b| b >= buckets && b != NO_BUCKET) {
+            return Err(EquityError::InvalidTable);
+        }
+        Ok(Self {
+            board_cards,
+            buckets,
+            boards,
+            table,
+        })
+    }
+}
+
+/// Bucket counts must fit below [`NO_BUCKET`], as [`BucketMap::from_bytes`] requires.
+fn check_config(cfg: &AbstractionConfig) -> Result<(), EquityError> {
+    if cfg.buckets == 0 || cfg.buckets >= NO_BUCKET as usize {
+        return Err(EquityError::InvalidBucketCount);
+    }
+    if cfg.bins == 0 {
+        return Err(EquityError::InvalidBinCount);
+    }
+    Ok(())
+}
+
+fn check_board_size(n: usize) -> Result<(), EquityError> {
+    if n < 3 {
+        return Err(EquityError::TooFewBoardCards(n));
+    }
+    if n > 5 {
+        return Err(EquityError::TooManyBoardCards(n));
+    }
+    Ok(())
+}
+
+/// Mask of a board that must have exactly `n` distinct cards.
+fn board_mask(board: &[u8], n: usize) -> Result<u64, EquityError> {
+    if board.len() < n {
+        return Err(EquityError::TooFewBoardCards(board.len()));
+    }
+    if board.len() > n {
+        return Err(EquityError::TooManyBoardCards(board.len()));
+    }
+    let mut mask = 0u64;
+    for &c in board {
+        if c >= 52 {
+            return Err(EquityError::CardOutOfRange(c));
+        }
+        if (mask >> c) & 1 != 0 {
+            return Err(EquityError::DuplicateCard(c));
+        }
+        mask |= 1u64 << c;
+    }
+    Ok(mask)
+}
+
+/// Weighted k-means under EMD with k-means++ seeding; returns each point's cluster.
+fn kmeans(cdfs: &[f32], weights: &[f64], bins: usize, cfg: &AbstractionConfig) -> Vec<u16> {
+    let n = weights.len();
+    let k = cfg.buckets.min(n);
+    if k == 0 {
+        return Vec::new();
+    }
+    let point = |i: usize| &cdfs[i * bins..(i + 1) * bins];
+    let mut rng = XorShift64::new(cfg.seed | 1);
+    let mut uniform = move || (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+
+    // k-means++: each new center drawn with probability ∝ weight × distance².
+    let pick = |scores: &[f64], u: f64| {
+        let total: f64 = scores.iter().sum();
+        let mut target = u * total;
+        for (i, &s) in scores.iter().enumerate() {
+            if target < s {
+                return i;
+            }
+            target -= s;
+        }
+        scores.iter().rposition(|&s| s > 0.0).unwrap_or(0)
+    };
+    let mut centers: Vec<f32> = point(pick(weights, uniform())).to_vec();
+    let mut nearest: Vec<f64> = (0..n).map(|i| emd(point(i), &centers[..bins]) as f64).collect();
+    while centers.len() / bins < k {
+        let scores: Vec<f64> = nearest.iter().zip(weights).map(|(&d, &w)| w * d * d).collect();
+        if scores.iter().all(|&s| s <= 0.0) {
+            // Fewer distinct histograms than buckets: the rest stay empty.
+            break;
+        }
+        let c = pick(&scores, uniform());
+        centers.extend_from_slice(point(c));
+        let new = &centers[centers.len() - bins..];
+        for (i, d) in nearest.iter_mut().enumerate() {
+            *d = d.min(emd(point(i), new) as f64);
+        }
+    }
+    let k_init = centers.len() / bins;
+
+    let mut assign = vec![u16::MAX; n];
+    for _ in 0..cfg.max_iters.max(1) {
+        let mut changed = false;
+        for (i, a) in assign.iter_mut().enumerate() {
+            let p = point(i);
+            let mut best = (f32::INFINITY, 0usize);
+            for c in 0..k_init {
+                let d = emd(p, &centers[c * bins..(c + 1) * bins]);
+                if d < best.0 {
+                    best = (d, c);
+                }
+            }
+            if *a != best.1 as u16 {
+                *a = best.1 as u16;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        let mut sums = vec![0.0f64; k_init * bins];
+        let mut mass = vec![0.0f64; k_init];
+        for (i, &a) in assign.iter().enumerate() {
+            let a = a as usize;
+            mass[a] += weights[i];
+            for (s, &v) in sums[a * bins..(a + 1) * bins].iter_mut().zip(point(i)) {
+                *s += weights[i] * v as f64;
+            }
+        }
+        for c in 0..k_init {
+            // An emptied cluster keeps its old center.
+            if mass[c] > 0.0 {
+                for (dst, &s) in centers[c * bins..(c + 1) * bins].iter_mut().zip(&sums[c * bins..(c + 1) * bins]) {
+                    *dst = (s / mass[c]) as f32;
+                }
+            }
+        }
+    }
+    assign
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_boards_count_isomorphism_classes() {
+        let flops = canonical_boards(3);
+        assert_eq!(flops.len(), 1_755);
+        assert_eq!(flops.iter().map(|&m| orbit_size(m)).sum::<u64>(), 22_100);
+        let mut seen = vec![false; HOLDINGS];
+        for a in 0u8..52 {
+            for b in (a + 1)..52 {
+                assert!(!seen[pair_index(a, b)]);
+                seen[pair_index(a, b)] = true;
+            }
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn buckets_are_suit_invariant_and_ordered() {
+        // Two turn boards: Ac Kd 7h 2s (rainbow) and 9c 8c 3c Jd (monotone-ish).
+        let boards: [&[u8]; 2] = [&[12, 24, 31, 39], &[7, 6, 1, 22]];
+        let cfg = AbstractionConfig {
+            bins: 10,
+            ..AbstractionConfig::new(6)
+        };
+        let map = BucketMap::build_on_boards(4, &boards, &cfg).unwrap();
+        assert_eq!(map.num_boards(), 2);
+
+        // Swapping clubs and spades maps situations onto themselves.
+        let swap = |c: u8| match c / 13 {
+            0 => c + 39,
+            3 => c - 39,
+            _ => c,
+        };
+        let board = [7u8, 6, 1, 22];
+        let swapped: Vec<u8> = board.iter().map(|&c| swap(c)).collect();
+        for a in 0u8..52 {
+            for b in (a + 1)..52 {
+                let got = map.bucket(&[a, b], &board);
+                if board.contains(&a) || board.contains(&b) {
+                    assert_eq!(got, None);
+                    continue;
+                }
+                let bucket = got.unwrap();
+                assert!((bucket as usize) < 6);
+                assert_eq!(map.bucket(&[swap(a), swap(b)], &swapped), Some(bucket));
+                assert_eq!(map.bucket(&[b, a], &[22, 1, 6, 7]), Some(bucket));
+            }
+        }
+        // Made flush vs 6-high: the stronger hand gets the higher bucket.
+        let flush = map.bucket(&[12, 11], &board).unwrap();
+        let air = map.bucket(&[13, 30], &board).unwrap();
+        assert!(flush > air, "{} vs {}", flush, air);
+
+        assert_eq!(map.bucket(&[12, 11], &[0, 13, 26, 40]), None);
+        assert_eq!(map.bucket(&[12, 11], &[7, 6, 1]), None);
+
+        let bytes = map.to_bytes();
+        assert_eq!(BucketMap::from_bytes(&bytes).unwrap(), map);
+        assert_eq!(BucketMap::from_bytes(&bytes[..bytes.len() - 1]), Err(EquityError::InvalidTable));
+    }
+
+    #[test]
+    fn build_rejects_bad_config() {
+        let boards: [&[u8]; 1] = [&[12, 24, 31, 39]];
+        for buckets in [0, NO_BUCKET as usize] {
+            let cfg = AbstractionConfig::new(buckets);
+            assert_eq!(BucketMap::build_on_boards(4, &boards, &cfg), Err(EquityError::InvalidBucketCount));
+            assert_eq!(BucketMap::build(5, &cfg), Err(EquityError::InvalidBucketCount));
+        }
+        let cfg = AbstractionConfig {
+            bins: 0,
+            ..AbstractionConfig::new(6)
+        };
+        assert_eq!(BucketMap::build_on_boards(4, &boards, &cfg), Err(EquityError::InvalidBinCount));
+        assert_eq!(BucketMap::build_on_boards(4, &[], &cfg), Err(EquityError::InvalidBinCount));
+    }
+}
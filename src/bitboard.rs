@@ -107,11 +107,11 @@ mod tests {
         let mut b = BitBoard4x13::new();
         let c = Card::new(Spades, Ace);
 
-        assert_eq!(b.add_card(c), false);
-        assert_eq!(b.add_card(c), true);
+        assert!(!b.add_card(c));
+        assert!(b.add_card(c));
 
         b.remove_card(c);
-        assert_eq!(b.add_card(c), false);
+        assert!(!b.add_card(c));
     }
 
     #[test]
@@ -0,0 +1,307 @@
+//! Blockers: how hero's hole cards reshape villain's range.
+//!
+//! Villain's combos that share a card with hero can't be dealt. Each villain combo
+//! is split into value or bluff by its place in the board's [`NutRanking`]: combos
+//! beating at least `value_percentile` of all holdings are value. Comparing
+//! villain's range with and without hero's cards removed shows which classes
+//! hero blocks and how the value share moves.
+
+use crate::equity::{validate_board, validate_inputs, EquityError};
+use crate::hand_class::HandClass;
+use crate::nuts::NutRanking;
+use crate::range::Range;
+
+/// Villain's weight in one starting-hand class.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ClassBlocking {
+    pub class: HandClass,
+    /// Weight live on the board.
+    pub weight: f64,
+    /// Part of it sharing a card with hero.
+    pub blocked: f64,
+}
+
+/// Hero's effect on a villain range.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockerReport {
+    pub hand: [u8; 2],
+    /// Classes villain holds on the board, in chart order.
+    pub classes: Vec<ClassBlocking>,
+    /// Villain's value and bluff weight on the board, before hero's cards are removed.
+    pub value: f64,
+    pub bluff: f64,
+    /// The part of each that hero blocks.
+    pub value_blocked: f64,
+    pub bluff_blocked: f64,
+}
+
+impl BlockerReport {
+    /// Villain's value share against a hero holding no cards of the range.
+    pub fn value_share(&self) -> f64 {
+        share(self.value, self.bluff)
+    }
+
+    /// Villain's value share once hero's cards are removed.
+    pub fn value_share_blocked(&self) -> f64 {
+        share(self.value - self.value_blocked, self.bluff - self.bluff_blocked)
+    }
+
+    /// Change in value share from hero's cards; negative when hero blocks value.
+    pub fn shift(&self) -> f64 {
+        self.value_share_blocked() - self.value_share()
+    }
+
+    /// Total weight hero blocks.
+    pub fn blocked(&self) -> f64 {
+        self.value_blocked + self.bluff_blocked
+    }
+}
+
+/// Blocker effect of one hero holding, as ranked by [`rank_blockers`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BlockerEffect {
+    pub hand: [u8; 2],
+    /// Villain's value share with the holding's cards removed.
+    pub value_share: f64,
+    /// Change from the unblocked value share.
+    pub shift: f64,
+    /// Villain weight blocked.
+    pub blocked: f64,
+}
+
+#[inline(always)]
+fn share(value: f64, bluff: f64) -> f64 {
+    let total = value + bluff;
+    if total > 0.0 {
+        value / total
+    } else {
+        0.0
+    }
+}
+
+#[inline]
+fn check_percentile(value_percentile: f64) -> Result<(), EquityError> {
+    if (0.0..=1.0).contains(&value_percentile) {
+        Ok(())
+    } else {
+        Err(EquityError::InvalidPercentile)
+    }
+}
+
+/// Villain's live combos on the board with a value flag each.
+fn split_range(
+    villain: &Range,
+    ranking: &NutRanking,
+    used: u64,
+    value_percentile: f64,
+) -> Result<Vec<([u8; 2], f64, bool)>, EquityError> {
+    let live = villain.live(used);
+    if live.is_empty() {
+        return Err(EquityError::EmptyRange);
+    }
+    Ok(live
+        .combos()
+        .iter()
+        .map(|c| {
+            let p = ranking.percentile(&c.hand).expect("live combo is ranked");
+            (c.hand, c.weight, p >= value_percentile)
+        })
+        .collect())
+}
+
+/// What `hero` blocks of `villain` on a 3-5 card `board`.
+pub fn blocker_report(
+    hero: &[u8; 2],
+    villain: &Range,
+    board: &[u8],
+    dead: &[u8],
+    value_percentile: f64,
+) -> Result<BlockerReport, EquityError> {
+    validate_inputs(hero, None, board, dead)?;
+    let used = validate_board(board, dead)?;
+    check_percentile(value_percentile)?;
+    let ranking = NutRanking::new(board, dead)?;
+    let combos = split_range(villain, &ranking, used, value_percentile)?;
+    let hero_mask = (1u64 << hero[0]) | (1u64 << hero[1]);
+
+    let mut classes: Vec<Option<ClassBlocking>> = vec![None; HandClass::COUNT];
+    let mut report = BlockerReport {
+        hand: *hero,
+        classes: Vec::new(),
+        value: 0.0,
+        bluff: 0.0,
+        value_blocked: 0.0,
+        bluff_blocked: 0.0,
+    };
+    for &(hand, w, value) in &combos {
+        let class = HandClass::from_hand(hand);
+        let entry = classes[class.index()].get_or_insert(ClassBlocking { class, weight: 0.0, blocked: 0.0 });
+        entry.weight += w;
+        let blocked = hero_mask & ((1u64 << hand[0]) | (1u64 << hand[1])) != 0;
+        if blocked {
+            entry.blocked += w;
+        }
+        match (value, blocked) {
+            (true, true) => {
+                report.value += w;
+                report.value_blocked += w;
+            }
+            (true, false) => report.value += w,
+            (false, true) => {
+                report.bluff += w;
+                report.bluff_blocked += w;
+            }
+            (false, false) => report.bluff += w,
+        }
+    }
+    report.classes = classes.into_iter().flatten().collect();
+    Ok(report)
+}
+
+/// Every hero holding live on a 3-5 card `board`, ranked by how far it lowers
+/// villain's value share: the best bluff-catchers (and bluffs) first.
+pub fn rank_blockers(
+    villain: &Range,
+    board: &[u8],
+    dead: &[u8],
+    value_percentile: f64,
+) -> Result<Vec<BlockerEffect>, EquityError> {
+    let used = validate_board(board, dead)?;
+    check_percentile(value_percentile)?;
+    let ranking = NutRanking::new(board, dead)?;
+    let combos = split_range(villain, &ranking, used, value_percentile)?;
+
+    // Value and bluff weight through each card.
+    let mut by_card = [[0.0f64; 2]; 52];
+    let mut value = 0.0;
+    let mut bluff = 0.0;
+    for &(hand, w, is_value) in &combos {
+        let k = usize::from(!is_value);
+        by_card[hand[0] as usize][k] += w;
+        by_card[hand[1] as usize][k] += w;
+        if is_value {
+            value += w;
+        } else {
+            bluff += w;
+        }
+    }
+    let base = share(value, bluff);
+
+    let mut out: Vec<BlockerEffect> = ranking
+        .tiers()
+        .iter()
+        .flat_map(|t| &t.hands)
+        .map(|&hand| {
+            let [a, b] = [hand[0] as usize, hand[1] as usize];
+            let mut blocked = [by_card[a][0] + by_card[b][0], by_card[a][1] + by_card[b][1]];
+            // A villain combo equal to the holding went through both cards.
+            if let Some(&(_, w, is_value)) = combos.iter().find(|c| c.0 == hand) {
+                blocked[usize::from(!is_value)] -= w;
+            }
+            let value_share = share(value - blocked[0], bluff - blocked[1]);
+            BlockerEffect {
+                hand,
+                value_share,
+                shift: value_share - base,
+                blocked: blocked[0] + blocked[1],
+            }
+        })
+        .collect();
+    out.sort_by(|x, y| x.shift.total_cmp(&y.shift).then(x.hand.cmp(&y.hand)));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::ids;
+
+    #[test]
+    fn nut_flush_blocker_on_flush_river() {
+        // Three hearts on the river; villain has flushes and missed straight draws.
+        let board = ids("Kh 8h 3h Td 2s");
+        let mut villain = Range::new();
+        for h in [["Ah", "5h"], ["Qh", "Jh"], ["9h", "7h"], ["Ac", "Qd"], ["Qs", "Jc"], ["9c", "7d"]] {
+            let a = ids(h[0])[0];
+            let b = ids(h[1])[0];
+            villain.add([a, b], 1.0).unwrap();
+        }
+
+        let ah = ids("Ah")[0];
+        let r = blocker_report(&[ah, ids("4c")[0]], &villain, &board, &[], 0.9).unwrap();
+        assert_eq!(r.value, 3.0);
+        assert_eq!(r.bluff, 3.0);
+        assert_eq!(r.value_blocked, 1.0);
+        assert_eq!(r.bluff_blocked, 0.0);
+        assert!((r.value_share() - 0.5).abs() < 1e-12);
+        assert!((r.value_share_blocked() - 2.0 / 5.0).abs() < 1e-12);
+        assert!(r.shift() < 0.0);
+        let a5s = r.classes.iter().find(|c| c.class.to_string() == "A5s").unwrap();
+        assert_eq!((a5s.weight, a5s.blocked), (1.0, 1.0));
+        assert_eq!(r.classes.len(), 6);
+
+        // Holding the ace of clubs blocks a bluff instead.
+        let ac = blocker_report(&[ids("Ac")[0], ids("4c")[0]], &villain, &board, &[], 0.9).unwrap();
+        assert!(ac.shift() > 0.0);
+
+        let ranked = rank_blockers(&villain, &board, &[], 0.9).unwrap();
+        assert_eq!(ranked.len(), 47 * 46 / 2);
+        assert!(ranked.windows(2).all(|w| w[0].shift <= w[1].shift));
+        // Agrees with the one-hand report.
+        for e in ranked.iter().step_by(97) {
+            let r = blocker_report(&e.hand, &villain, &board, &[], 0.9).unwrap();
+            assert!((e.shift - r.shift()).abs() < 1e-12);
+            assert!((e.blocked - r.blocked()).abs() < 1e-12);
+        }
+        // Two value combos blocked at once is as good as it gets here.
+        assert!((ranked[0].value_share - 1.0 / 4.0).abs() < 1e-12);
+
+        assert_eq!(
+            blocker_report(&[ah, 0], &Range::from_hands(&[[board[0], 1]]).unwrap(), &board, &[], 0.9),
+            Err(EquityError::EmptyRange)
+        );
+    }
+
+    #[test]
+    fn flush_blocker_on_turn() {
+        // Same range a street earlier: the flushes are already in the top tenth.
+        let board = ids("Kh 8h 3h Td");
+        let mut villain = Range::new();
+        for h in [["Ah", "5h"], ["Qh", "Jh"], ["9h", "7h"], ["Ac", "Qd"], ["Qs", "Jc"], ["9c", "7d"]] {
+            villain.add([ids(h[0])[0], ids(h[1])[0]], 1.0).unwrap();
+        }
+
+        let hero = [ids("Ah")[0], ids("4c")[0]];
+        let r = blocker_report(&hero, &villain, &board, &[], 0.9).unwrap();
+        assert_eq!((r.value, r.bluff), (3.0, 3.0));
+        assert_eq!((r.value_blocked, r.bluff_blocked), (1.0, 0.0));
+        assert!((r.value_share_blocked() - 2.0 / 5.0).abs() < 1e-12);
+
+        let ranked = rank_blockers(&villain, &board, &[], 0.9).unwrap();
+        assert_eq!(ranked.len(), 48 * 47 / 2);
+        for e in ranked.iter().step_by(101) {
+            let r = blocker_report(&e.hand, &villain, &board, &[], 0.9).unwrap();
+            assert!((e.shift - r.shift()).abs() < 1e-12);
+        }
+
+        // Both ends are accepted: every combo is value at 0.0, only a lone nuts at 1.0.
+        let all = blocker_report(&hero, &villain, &board, &[], 0.0).unwrap();
+        assert_eq!((all.value, all.bluff), (6.0, 0.0));
+        let top = blocker_report(&hero, &villain, &board, &[], 1.0).unwrap();
+        assert_eq!((top.value, top.bluff), (0.0, 6.0));
+    }
+
+    #[test]
+    fn value_percentile_out_of_range() {
+        let board = ids("Kh 8h 3h Td");
+        let villain = Range::from_hands(&[[ids("Ah")[0], ids("5h")[0]]]).unwrap();
+        let hero = [ids("Ac")[0], ids("4c")[0]];
+        for p in [f64::NAN, -0.1, 1.5, f64::INFINITY] {
+            assert_eq!(
+                blocker_report(&hero, &villain, &board, &[], p),
+                Err(EquityError::InvalidPercentile)
+            );
+            assert_eq!(rank_blockers(&villain, &board, &[], p), Err(EquityError::InvalidPercentile));
+        }
+    }
+}
@@ -232,7 +232,7 @@ impl FromStr for Card {
 /// Parse space-separated cards.
 pub fn parse_hand(s: &str) -> Result<Vec<Card>, String> {
     s.split_whitespace()
-        .map(|card_str| Card::from_str(card_str))
+        .map(Card::from_str)
         .collect()
 }
 
@@ -276,6 +276,12 @@ pub fn parse_board(s: &str) -> Result<Vec<Card>, String> {
     Ok(cards)
 }
 
+/// Card ids of a space-separated board string.
+#[cfg(test)]
+pub(crate) fn ids(s: &str) -> Vec<u8> {
+    parse_board(s).unwrap().iter().map(|c| c.id()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
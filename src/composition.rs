@@ -0,0 +1,325 @@
+//! Range composition on a board: how a range's weight splits into made hands
+//! and draws.
+//!
+//! Every live combo is read with [`MadeHand`] and, on the flop and turn, with
+//! [`Draws`]. Made-hand classes split the range; draw classes overlap them and
+//! each other (a flush draw with top pair counts in both), as in the usual
+//! range-analysis tools. Any set of classes can be cut back out as a sub-range.
+
+use std::fmt;
+
+use crate::draws::{Draws, StraightDraw};
+use crate::equity::{validate_board, EquityError};
+use crate::made_hand::{classify, MadeHand};
+use crate::range::Range;
+use crate::BitBoard4x13;
+
+/// A row of the composition report.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ComboClass {
+    // Made hands, strongest first; each combo is in exactly one.
+    StraightFlush,
+    Quads,
+    FullHouse,
+    Flush,
+    Straight,
+    Set,
+    Trips,
+    TwoPair,
+    Overpair,
+    TopPair,
+    /// Pocket pair between the top and bottom board cards.
+    PocketPair,
+    MiddlePair,
+    BottomPair,
+    Underpair,
+    /// High card, or hole cards that only add kickers to the board.
+    NoMadeHand,
+    // Draws, which overlap the made hands.
+    NutFlushDraw,
+    FlushDraw,
+    /// Open-ended straight draws and double gutshots.
+    OpenEnded,
+    Gutshot,
+    /// A flush draw with a straight draw.
+    ComboDraw,
+    BackdoorFlushDraw,
+    BackdoorStraightDraw,
+    /// Both hole cards above the board.
+    TwoOvercards,
+    /// No made hand and no flush or straight draw.
+    Air,
+}
+
+impl ComboClass {
+    /// Every class in report order.
+    pub const ALL: [ComboClass; 24] = [
+        ComboClass::StraightFlush,
+        ComboClass::Quads,
+        ComboClass::FullHouse,
+        ComboClass::Flush,
+        ComboClass::Straight,
+        ComboClass::Set,
+        ComboClass::Trips,
+        ComboClass::TwoPair,
+        ComboClass::Overpair,
+        ComboClass::TopPair,
+        ComboClass::PocketPair,
+        ComboClass::MiddlePair,
+        ComboClass::BottomPair,
+        ComboClass::Underpair,
+        ComboClass::NoMadeHand,
+        ComboClass::NutFlushDraw,
+        ComboClass::FlushDraw,
+        ComboClass::OpenEnded,
+        ComboClass::Gutshot,
+        ComboClass::ComboDraw,
+        ComboClass::BackdoorFlushDraw,
+        ComboClass::BackdoorStraightDraw,
+        ComboClass::TwoOvercards,
+        ComboClass::Air,
+    ];
+
+    /// Made-hand class of a [`MadeHand`].
+    pub fn of_made_hand(m: MadeHand) -> Self {
+        match m {
+            MadeHand::StraightFlush => ComboClass::StraightFlush,
+            MadeHand::Quads => ComboClass::Quads,
+            MadeHand::FullHouse => ComboClass::FullHouse,
+            MadeHand::Flush { .. } => ComboClass::Flush,
+            MadeHand::Straight { .. } => ComboClass::Straight,
+            MadeHand::Set => ComboClass::Set,
+            MadeHand::Trips(_) => ComboClass::Trips,
+            MadeHand::TwoPair(_) => ComboClass::TwoPair,
+            MadeHand::Overpair => ComboClass::Overpair,
+            MadeHand::TopPair(_) => ComboClass::TopPair,
+            MadeHand::PocketPair => ComboClass::PocketPair,
+            MadeHand::MiddlePair(_) => ComboClass::MiddlePair,
+            MadeHand::BottomPair(_) => ComboClass::BottomPair,
+            MadeHand::Underpair => ComboClass::Underpair,
+            MadeHand::HighCard | MadeHand::PlayingTheBoard => ComboClass::NoMadeHand,
+        }
+    }
+}
+
+impl fmt::Display for ComboClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ComboClass::StraightFlush => "straight flush",
+            ComboClass::Quads => "quads",
+            ComboClass::FullHouse => "full house",
+            ComboClass::Flush => "flush",
+            ComboClass::Straight => "straight",
+            ComboClass::Set => "set",
+            ComboClass::Trips => "trips",
+            ComboClass::TwoPair => "two pair",
+            ComboClass::Overpair => "overpair",
+            ComboClass::TopPair => "top pair",
+            ComboClass::PocketPair => "pocket pair below top",
+            ComboClass::MiddlePair => "middle pair",
+            ComboClass::BottomPair => "bottom pair",
+            ComboClass::Underpair => "underpair",
+            ComboClass::NoMadeHand => "no made hand",
+            ComboClass::NutFlushDraw => "nut flush draw",
+            ComboClass::FlushDraw => "flush draw",
+            ComboClass::OpenEnded => "OESD / double gutshot",
+            ComboClass::Gutshot => "gutshot",
+            ComboClass::ComboDraw => "combo draw",
+            ComboClass::BackdoorFlushDraw => "backdoor flush draw",
+            ComboClass::BackdoorStraightDraw => "backdoor straight draw",
+            ComboClass::TwoOvercards => "two overcards",
+            ComboClass::Air => "air",
+        })
+    }
+}
+
+/// One live combo of the range, read against the board.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ComboInfo {
+    pub hand: [u8; 2],
+    pub weight: f64,
+    pub made: MadeHand,
+    /// `None` on the river.
+    pub draws: Option<Draws>,
+}
+
+impl ComboInfo {
+    /// Whether the combo counts in `class`.
+    pub fn is(&self, class: ComboClass) -> bool {
+        let flush_draw = self.draws.and_then(|d| d.flush_draw);
+        let straight_draw = self.draws.map_or(StraightDraw::None, |d| d.straight_draw);
+        match class {
+            ComboClass::NutFlushDraw => flush_draw.is_some_and(|f| f.nut),
+            ComboClass::FlushDraw => flush_draw.is_some(),
+            ComboClass::OpenEnded => straight_draw >= StraightDraw::DoubleGutter,
+            ComboClass::Gutshot => straight_draw == StraightDraw::Gutshot,
+            ComboClass::ComboDraw => self.draws.is_some_and(|d| d.is_combo_draw()),
+            ComboClass::BackdoorFlushDraw => self.draws.is_some_and(|d| d.backdoor_flush),
+            ComboClass::BackdoorStraightDraw => self.draws.is_some_and(|d| d.backdoor_straight),
+            ComboClass::TwoOvercards => self.draws.is_some_and(|d| d.overcards == 2),
+            ComboClass::Air => {
+                !self.made.is_pair_or_better() && !self.draws.is_some_and(|d| d.has_draw())
+            }
+            made => ComboClass::of_made_hand(self.made) == made,
+        }
+    }
+}
+
+/// A range on a board, combo by combo.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeComposition {
+    combos: Vec<ComboInfo>,
+    total: f64,
+}
+
+impl RangeComposition {
+    /// Read every combo of `range` with positive weight that avoids a 3-5 card
+    /// `board` and `dead`.
+    pub fn new(range: &Range, board: &[u8], dead: &[u8]) -> Result<Self, EquityError> {
+        let used = validate_board(board, dead)?;
+        let mut bb = BitBoard4x13::new();
+        for &c in board {
+            bb.add_id(c);
+        }
+        let combos: Vec<ComboInfo> = range
+            .live(used)
+            .combos()
+            .iter()
+            .map(|c| {
+                let mut hole = BitBoard4x13::new();
+                hole.add_id(c.hand[0]);
+                hole.add_id(c.hand[1]);
+                ComboInfo {
+                    hand: c.hand,
+                    weight: c.weight,
+                    made: classify(&c.hand, &bb),
+                    draws: (board.len() < 5).then(|| Draws::from_bitboards(&hole, &bb)),
+                }
+            })
+            .collect();
+        let total = combos.iter().map(|c| c.weight).sum();
+        Ok(Self { combos, total })
+    }
+
+    pub fn combos(&self) -> &[ComboInfo] {
+        &self.combos
+    }
+
+    /// Weight of the live range.
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    /// Weight of the combos in `class` (the number of combos when weights are 1).
+    pub fn weight(&self, class: ComboClass) -> f64 {
+        self.combos.iter().filter(|c| c.is(class)).map(|c| c.weight).sum()
+    }
+
+    /// Share of the live range in `class`.
+    pub fn share(&self, class: ComboClass) -> f64 {
+        if self.total > 0.0 {
+            self.weight(class) / self.total
+        } else {
+            0.0
+        }
+    }
+
+    /// Non-empty classes with their weight and share, in report order.
+    pub fn rows(&self) -> Vec<(ComboClass, f64, f64)> {
+        ComboClass::ALL
+            .iter()
+            .map(|&class| (class, self.weight(class), self.share(class)))
+            .filter(|&(_, w, _)| w > 0.0)
+            .collect()
+    }
+
+    /// The combos in any of `classes`, with their weights.
+    pub fn sub_range(&self, classes: &[ComboClass]) -> Range {
+        self.filter(|c| classes.iter().any(|&k| c.is(k)))
+    }
+
+    /// The combos passing `keep`, with their weights.
+    pub fn filter(&self, keep: impl Fn(&ComboInfo) -> bool) -> Range {
+        let mut out = Range::new();
+        for c in self.combos.iter().filter(|c| keep(c)) {
+            out.add(c.hand, c.weight).expect("combo came from a valid range");
+        }
+        out
+    }
+}
+
+impl fmt::Display for RangeComposition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (class, w, share) in self.rows() {
+            writeln!(f, "{:24} {:>8.1} {:>6.1}%", class.to_string(), w, share * 100.0)?;
+        }
+        write!(f, "{:24} {:>8.1}", "total", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::ids;
+
+    #[test]
+    fn random_range_on_a_flop() {
+        let board = ids("Kh 9h 4c");
+        let comp = RangeComposition::new(&Range::random(), &board, &[]).unwrap();
+        assert_eq!(comp.total(), 1176.0);
+
+        // Made classes split the range exactly.
+        let made: f64 = ComboClass::ALL[..15].iter().map(|&c| comp.weight(c)).sum();
+        assert_eq!(made, comp.total());
+
+        assert_eq!(comp.weight(ComboClass::Set), 9.0);
+        assert_eq!(comp.weight(ComboClass::TwoPair), 27.0);
+        // Only aces beat the king; QQ-TT and 88-55 sit between the board cards.
+        assert_eq!(comp.weight(ComboClass::Overpair), 6.0);
+        assert_eq!(comp.weight(ComboClass::PocketPair), 7.0 * 6.0);
+        // Each king with any of the 40 cards that pair nothing else.
+        assert_eq!(comp.weight(ComboClass::TopPair), 3.0 * 40.0);
+        // Two hearts among the eleven left.
+        assert_eq!(comp.weight(ComboClass::FlushDraw), 55.0);
+        // Only the ace of hearts makes the nut draw.
+        assert_eq!(comp.weight(ComboClass::NutFlushDraw), 10.0);
+        assert!(comp.weight(ComboClass::ComboDraw) > 0.0);
+        assert!(comp.share(ComboClass::Air) > 0.3);
+
+        let sub = comp.sub_range(&[ComboClass::Set, ComboClass::TwoPair]);
+        assert_eq!(sub.len(), 36);
+        let draws = comp.filter(|c| c.is(ComboClass::FlushDraw) && c.made.is_pair_or_better());
+        assert!(draws.combos().iter().all(|c| c.hand[0] / 13 == 2 && c.hand[1] / 13 == 2));
+
+        let text = comp.to_string();
+        assert!(text.lines().any(|l| l.starts_with("set") && l.contains("0.8%")));
+    }
+
+    #[test]
+    fn weighted_range_on_the_river() {
+        let board = ids("Kh 9h 4c 2d 7s");
+        let mut range = Range::new();
+        range.add([ids("Kd")[0], ids("Ks")[0]], 0.5).unwrap();
+        range.add([ids("Ah")[0], ids("Qh")[0]], 1.0).unwrap();
+        range.add([ids("Kc")[0], ids("4s")[0]], 2.0).unwrap();
+        range.add([ids("Kh")[0], ids("Qs")[0]], 1.0).unwrap();
+        let comp = RangeComposition::new(&range, &board, &[]).unwrap();
+        assert_eq!(comp.total(), 3.5);
+        assert!(comp.combos().iter().all(|c| c.draws.is_none()));
+        assert_eq!(comp.weight(ComboClass::Set), 0.5);
+        assert_eq!(comp.weight(ComboClass::TwoPair), 2.0);
+        assert_eq!(comp.weight(ComboClass::Air), 1.0);
+        assert_eq!(comp.rows().len(), 4);
+        assert!((comp.share(ComboClass::TwoPair) - 2.0 / 3.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wheel_draw_is_open_ended() {
+        let board = ids("3h 2s Kc");
+        let h = ids("5c 4d");
+        let range = Range::from_hands(&[[h[0], h[1]]]).unwrap();
+        let comp = RangeComposition::new(&range, &board, &[]).unwrap();
+        assert_eq!(comp.weight(ComboClass::OpenEnded), 1.0);
+        assert_eq!(comp.weight(ComboClass::Gutshot), 0.0);
+    }
+}
@@ -0,0 +1,281 @@
+//! Draws: what hero's hole cards are drawing to on the flop or turn.
+//!
+//! Everything here is read off the rank and suit masks of hero's cards and the
+//! board. A draw only counts if hero's hole cards play in the finished hand: a
+//! card that puts a straight on board is not an out, and four of a suit on board
+//! with none in hero's hand is not a flush draw.
+
+use crate::bitboard::{BitBoard4x13, MASK13};
+use crate::card::Suit;
+use crate::equity::{validate_inputs, EquityError};
+use crate::lut13::{hibit13, popcnt13, straight_end13};
+use crate::outs::check_street;
+
+/// Straight draw by the number of ranks that complete it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum StraightDraw {
+    None,
+    /// One completing rank (inside straight, or one-ended like A-K-Q-J).
+    Gutshot,
+    /// Two completing ranks without four in a row (9-7-6-5-3).
+    DoubleGutter,
+    /// Four in a row that either end completes.
+    OpenEnded,
+}
+
+/// Four cards of a suit with at least one of hero's.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FlushDraw {
+    pub suit: Suit,
+    /// Hero holds the highest card of the suit not on board.
+    pub nut: bool,
+    /// Unseen cards of the suit.
+    pub outs: u8,
+}
+
+/// Hero's draws on a flop or turn.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Draws {
+    pub flush_draw: Option<FlushDraw>,
+    /// Three of a suit with one of hero's on the flop, and no flush draw.
+    pub backdoor_flush: bool,
+    pub straight_draw: StraightDraw,
+    /// Unseen cards of the completing ranks.
+    pub straight_outs: u8,
+    /// On the flop with no straight draw, some turn and river make hero a straight.
+    pub backdoor_straight: bool,
+    /// Hole cards above every board card, if hero has no pair.
+    pub overcards: u8,
+    /// Distinct cards completing the flush or straight draw.
+    pub outs: u8,
+    /// Those cards as a card-id mask.
+    pub out_cards: u64,
+}
+
+impl Draws {
+    /// Draws of `hole` on a 3 or 4 card `board`.
+    pub fn new(hole: &[u8; 2], board: &[u8]) -> Result<Self, EquityError> {
+        check_street(board)?;
+        validate_inputs(hole, None, board, &[])?;
+        let mut h = BitBoard4x13::new();
+        h.add_id(hole[0]);
+        h.add_id(hole[1]);
+        let mut b = BitBoard4x13::new();
+        for &c in board {
+            b.add_id(c);
+        }
+        Ok(Self::from_bitboards(&h, &b))
+    }
+
+    /// Draws of the hole cards in `hole` on `board`; backdoors need a three-card board.
+    pub fn from_bitboards(hole: &BitBoard4x13, board: &BitBoard4x13) -> Self {
+        let hs = hole.suits_array();
+        let bs = board.suits_array();
+        let flop = bs.iter().map(|&m| popcnt13(m)).sum::<u8>() == 3;
+
+        let mut flush_draw = None;
+        let mut backdoor_flush = false;
+        let mut flush_cards = 0u64;
+        for s in 0..4 {
+            if hs[s] == 0 {
+                continue;
+            }
+            match popcnt13(hs[s] | bs[s]) {
+                4 => {
+                    let free = !(hs[s] | bs[s]) & MASK13;
+                    let top_open = hibit13(!bs[s] & MASK13);
+                    flush_draw = Some(FlushDraw {
+                        suit: Suit::ALL[s],
+                        nut: hibit13(hs[s]) == top_open,
+                        outs: popcnt13(free),
+                    });
+                    flush_cards = (free as u64) << (13 * s);
+                }
+                3 if flop => backdoor_flush = true,
+                _ => {}
+            }
+        }
+        if flush_draw.is_some() {
+            backdoor_flush = false;
+        }
+
+        let hr = hole.ranks_any();
+        let br = board.ranks_any();
+        let all = hr | br;
+        // A straight counts for hero only if it beats what the board makes with the same cards.
+        let hero_straight = |extra: u16| straight_end13(all | extra) > straight_end13(br | extra);
+
+        let mut straight_draw = StraightDraw::None;
+        let mut completing = 0u16;
+        let mut backdoor_straight = false;
+        if straight_end13(all) < 0 {
+            for r in 0..13 {
+                let bit = 1u16 << r;
+                if all & bit == 0 && hero_straight(bit) {
+                    completing |= bit;
+                }
+            }
+            straight_draw = match popcnt13(completing) {
+                0 => StraightDraw::None,
+                1 => StraightDraw::Gutshot,
+                _ if four_in_a_row(all) => StraightDraw::OpenEnded,
+                _ => StraightDraw::DoubleGutter,
+            };
+            if flop && completing == 0 {
+                backdoor_straight = (0..13).any(|r1| {
+                    (r1 + 1..13).any(|r2| {
+                        let extra = (1u16 << r1) | (1u16 << r2);
+                        all & extra == 0 && hero_straight(extra)
+                    })
+                });
+            }
+        }
+        let mut straight_cards = 0u64;
+        for s in 0..4 {
+            straight_cards |= (completing as u64) << (13 * s);
+        }
+
+        let paired = popcnt13(hr) < 2 || hr & br != 0;
+        let overcards = if paired {
+            0
+        } else {
+            let top = hibit13(br);
+            (0..13).filter(|&r| hr & (1 << r) != 0 && r as i8 > top).count() as u8
+        };
+
+        let out_cards = flush_cards | straight_cards;
+        Draws {
+            flush_draw,
+            backdoor_flush,
+            straight_draw,
+            straight_outs: straight_cards.count_ones() as u8,
+            backdoor_straight,
+            overcards,
+            outs: out_cards.count_ones() as u8,
+            out_cards,
+        }
+    }
+
+    /// A flush draw together with a straight draw.
+    pub fn is_combo_draw(&self) -> bool {
+        self.flush_draw.is_some() && self.straight_draw != StraightDraw::None
+    }
+
+    /// A flush or straight draw, backdoors aside.
+    pub fn has_draw(&self) -> bool {
+        self.flush_draw.is_some() || self.straight_draw != StraightDraw::None
+    }
+}
+
+/// Four consecutive ranks with a rank free at both ends: 2-3-4-5 (open to the ace) up to
+/// T-J-Q-K; J-Q-K-A and A-2-3-4 are one-ended.
+#[inline]
+fn four_in_a_row(ranks: u16) -> bool {
+    (0..=8).any(|lo| {
+        let run = 0xF << lo;
+        ranks & run == run
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::ids;
+
+    fn draws(hole: &str, board: &str) -> Draws {
+        let h = ids(hole);
+        Draws::new(&[h[0], h[1]], &ids(board)).unwrap()
+    }
+
+    #[test]
+    fn flush_draws() {
+        let d = draws("Ah 5h", "Kh 9h 2c");
+        let f = d.flush_draw.unwrap();
+        assert_eq!(f.suit, Suit::Hearts);
+        assert!(f.nut);
+        assert_eq!(f.outs, 9);
+        assert_eq!(d.outs, 9);
+        assert!(!d.backdoor_flush);
+
+        // The ace is on board, so the king is the nut draw and the queen is not.
+        assert!(draws("Kh 5h", "Ah 9h 2c").flush_draw.unwrap().nut);
+        assert!(!draws("Qh 5h", "Ah 9h 2c").flush_draw.unwrap().nut);
+        // One card on a three-flush board.
+        let d = draws("Qh 5c", "Ah 9h 2h");
+        assert_eq!(d.flush_draw.map(|f| f.outs), Some(9));
+        // No hole card of the suit, or the flush already made: no draw.
+        assert_eq!(draws("Qc 5c", "Ah 9h 2h Kh").flush_draw, None);
+        assert_eq!(draws("Qh 5h", "Ah 9h 2h").flush_draw, None);
+
+        let d = draws("Ah 5c", "Kh 9h 2c");
+        assert!(d.backdoor_flush && d.flush_draw.is_none());
+        assert!(!draws("Ah 5c", "Kh 9h 2c 3d").backdoor_flush);
+    }
+
+    #[test]
+    fn straight_draws() {
+        let d = draws("9c 8d", "7h 6s 2c");
+        assert_eq!(d.straight_draw, StraightDraw::OpenEnded);
+        assert_eq!(d.straight_outs, 8);
+
+        let d = draws("9c 7d", "5h 3s Kc");
+        assert_eq!(d.straight_draw, StraightDraw::None);
+        let d = draws("9c 7d", "6h 5s Kc");
+        assert_eq!(d.straight_draw, StraightDraw::Gutshot);
+        assert_eq!(d.straight_outs, 4);
+
+        let d = draws("9c 7d", "6h 5s 3c");
+        assert_eq!(d.straight_draw, StraightDraw::DoubleGutter);
+        assert_eq!(d.straight_outs, 8);
+
+        // One-ended: only a ten completes.
+        assert_eq!(draws("Ac Kd", "Qh Js 2c").straight_draw, StraightDraw::Gutshot);
+        // 2-3-4-5 is open at both ends: an ace or a six.
+        let d = draws("5c 4d", "3h 2s Kc");
+        assert_eq!(d.straight_draw, StraightDraw::OpenEnded);
+        assert_eq!(d.straight_outs, 8);
+        // T-J-Q-K is open; J-Q-K-A is not.
+        assert_eq!(draws("Kc Qd", "Jh Ts 2c").straight_draw, StraightDraw::OpenEnded);
+        assert!(four_in_a_row(0xF));
+        assert!(four_in_a_row(0xF << 8));
+        assert!(!four_in_a_row(0xF << 9));
+        // A card that only puts the straight on board is no out: 4 gives 45678 to everyone.
+        let d = draws("Tc 2d", "5h 6s 7c 8d");
+        assert_eq!(d.straight_draw, StraightDraw::Gutshot);
+        assert_eq!(d.out_cards, (0..4).map(|s| 1u64 << (13 * s + 7)).sum());
+
+        // Made straight: no draw reported.
+        assert_eq!(draws("9c 8d", "7h 6s 5c").straight_draw, StraightDraw::None);
+
+        assert!(draws("9c 8d", "6h 2s Kc").backdoor_straight);
+        assert!(!draws("9c 8d", "7h 6s 2c").backdoor_straight);
+        // Q-T runs out a straight the 8 takes no part in.
+        assert!(!draws("2c 8d", "Jh Ks Ac").backdoor_straight);
+        assert!(draws("Ac 8d", "4h Js Kc").backdoor_straight);
+    }
+
+    #[test]
+    fn combos_and_overcards() {
+        // Jh Th on 9h 8h 2c: open-ender plus flush draw, 15 distinct outs.
+        let d = draws("Jh Th", "9h 8h 2c");
+        assert!(d.is_combo_draw());
+        assert_eq!(d.straight_outs, 8);
+        assert_eq!(d.flush_draw.unwrap().outs, 9);
+        assert_eq!(d.outs, 15);
+
+        assert_eq!(draws("Ac Kd", "9h 7s 2c").overcards, 2);
+        assert_eq!(draws("Ac 5d", "9h 7s 2c").overcards, 1);
+        assert_eq!(draws("Ac Ad", "9h 7s 2c").overcards, 0);
+        assert_eq!(draws("Ac 9d", "9h 7s 2c").overcards, 0);
+        assert!(!draws("Ac 5d", "9h 7s 2c").has_draw());
+
+        assert_eq!(
+            Draws::new(&[0, 1], &ids("9h 7s")),
+            Err(EquityError::TooFewBoardCards(2))
+        );
+        assert_eq!(
+            Draws::new(&[0, 1], &ids("9h 7s 2c 3c 4c")),
+            Err(EquityError::TooManyBoardCards(5))
+        );
+    }
+}
@@ -1,7 +1,66 @@
 //! Equity calculation: Monte Carlo simulation and exact enumeration.
 
+use crate::range::Range;
 use crate::{evaluate_u32, BitBoard4x13};
 
+mod adaptive;
+mod breakdown;
+mod crn;
+mod iso;
+mod odds;
+#[cfg(feature = "parallel")]
+mod par;
+mod sampling;
+
+use iso::for_each_runout_class;
+use sampling::RunoutSampler;
+pub use sampling::Sampling;
+pub use breakdown::{
+    equity_exact_multiway_breakdown_checked,
+    equity_exact_vs_hand_breakdown_checked,
+    equity_mc_multiway_breakdown_checked,
+    equity_mc_ranges_multiway_breakdown_checked,
+    equity_mc_vs_hand_breakdown_checked,
+    equity_mc_vs_random_breakdown_checked,
+    CategoryBreakdown,
+};
+pub use odds::{
+    category_odds_checked,
+    category_odds_exact_checked,
+    category_odds_mc_checked,
+    category_odds_seven_cards,
+    CategoryOdds,
+};
+pub use crn::{equity_mc_crn_checked, equity_mc_crn_checked_with_dead, equity_mc_crn_sampled_checked, PairedEquity};
+pub use adaptive::{
+    equity_mc_adaptive,
+    equity_mc_adaptive_multiway,
+    AdaptiveMultiWayResult,
+    AdaptiveResult,
+    StopReason,
+    StopRule,
+};
+#[cfg(feature = "parallel")]
+pub use par::{
+    equity_exact_multiway_checked_par,
+    equity_exact_multiway_pot_share_checked_par,
+    equity_exact_vs_hand_checked_par,
+    equity_exact_vs_random_checked_par,
+    equity_mc_multiway_checked_par,
+    equity_mc_multiway_pot_share_checked_par,
+    equity_mc_multiway_sampled_checked_par,
+    equity_mc_ranges_multiway_checked_par,
+    equity_mc_ranges_multiway_pot_share_checked_par,
+    equity_mc_ranges_multiway_sampled_checked_par,
+    equity_mc_vs_hand_checked_par,
+    equity_mc_vs_hand_sampled_checked_par,
+    equity_mc_vs_random_checked_par,
+    equity_mc_vs_random_sampled_checked_par,
+    equity_mc_vs_random_multiway_checked_par,
+    equity_mc_vs_random_multiway_pot_share_checked_par,
+    equity_mc_vs_random_multiway_sampled_checked_par,
+};
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Outcome {
     HeroWin,
@@ -9,6 +68,9 @@ pub enum Outcome {
     VillainWin,
 }
 
+/// Most players any equity function accepts.
+pub const MAX_PLAYERS: usize = 9;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
 pub struct EquityCounts {
     pub win: u64,
@@ -23,6 +85,7 @@ impl EquityCounts {
     }
 
     /// "Equity" as win + 0.5*tie, normalized to [0,1].
+    /// Multi-way chops are split exactly by [`MultiWayCounts::equity`].
     pub fn equity(&self) -> f64 {
         let t = self.total() as f64;
         if t == 0.0 {
@@ -31,6 +94,29 @@ impl EquityCounts {
         (self.win as f64 + 0.5 * self.tie as f64) / t
     }
 
+    /// Per-trial variance of the pot share.
+    pub fn variance(&self) -> f64 {
+        variance(self.total(), self.win as f64 + 0.25 * self.tie as f64, self.equity())
+    }
+
+    /// Standard error of `equity()` (infinite with fewer than 2 trials).
+    pub fn std_error(&self) -> f64 {
+        std_error(self.total(), self.variance())
+    }
+
+    /// Normal-approximation interval `equity ± z * std_error`, clamped to [0,1].
+    /// Use z = 1.96 for 95%.
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        normal_interval(self.equity(), self.std_error(), z)
+    }
+
+    /// Wilson score interval, treating equity as a binomial proportion.
+    /// Ties only reduce the true variance, so this is slightly conservative;
+    /// unlike the normal interval it does not collapse when equity is 0 or 1.
+    pub fn wilson_interval(&self, z: f64) -> (f64, f64) {
+        wilson_interval(self.total(), self.equity(), z)
+    }
+
     pub fn probs(&self) -> (f64, f64, f64) {
         let t = self.total() as f64;
         if t == 0.0 {
@@ -44,13 +130,145 @@ impl EquityCounts {
     }
 }
 
+impl std::ops::AddAssign for EquityCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.win += other.win;
+        self.tie += other.tie;
+        self.lose += other.lose;
+    }
+}
+
+/// One player's multi-way result: the raw [`EquityCounts`] (reachable through
+/// `Deref`) plus ties by the number of players splitting the pot.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct MultiWayCounts {
+    pub counts: EquityCounts,
+    /// `split_ways[k]` counts k-way chops.
+    pub split_ways: [u64; MAX_PLAYERS + 1],
+}
+
+impl MultiWayCounts {
+    /// Pots won, with a k-way chop counted as 1/k of a pot.
+    pub fn pot_share(&self) -> f64 {
+        let mut share = self.counts.win as f64;
+        for (k, &n) in self.split_ways.iter().enumerate().skip(2) {
+            share += n as f64 / k as f64;
+        }
+        share
+    }
+
+    /// Average pot share, normalized to [0,1].
+    pub fn equity(&self) -> f64 {
+        let t = self.counts.total() as f64;
+        if t == 0.0 {
+            return 0.0;
+        }
+        self.pot_share() / t
+    }
+
+    /// Per-trial variance of the pot share.
+    pub fn variance(&self) -> f64 {
+        let mut sq = self.counts.win as f64;
+        for (k, &n) in self.split_ways.iter().enumerate().skip(2) {
+            sq += n as f64 / (k * k) as f64;
+        }
+        variance(self.counts.total(), sq, self.equity())
+    }
+
+    /// Standard error of `equity()` (infinite with fewer than 2 trials).
+    pub fn std_error(&self) -> f64 {
+        std_error(self.counts.total(), self.variance())
+    }
+
+    /// Normal-approximation interval `equity ± z * std_error`, clamped to [0,1].
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        normal_interval(self.equity(), self.std_error(), z)
+    }
+
+    /// Wilson score interval, as for [`EquityCounts::wilson_interval`].
+    pub fn wilson_interval(&self, z: f64) -> (f64, f64) {
+        wilson_interval(self.counts.total(), self.equity(), z)
+    }
+}
+
+impl std::ops::Deref for MultiWayCounts {
+    type Target = EquityCounts;
+
+    fn deref(&self) -> &EquityCounts {
+        &self.counts
+    }
+}
+
+impl std::ops::AddAssign for MultiWayCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.counts += other.counts;
+        for (a, b) in self.split_ways.iter_mut().zip(other.split_ways) {
+            *a += b;
+        }
+    }
+}
+
+/// Variance from the sum of squared pot shares over `n` trials.
+#[inline]
+fn variance(n: u64, sum_sq: f64, mean: f64) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    (sum_sq / n as f64 - mean * mean).max(0.0)
+}
+
+#[inline]
+fn std_error(n: u64, variance: f64) -> f64 {
+    if n < 2 {
+        return f64::INFINITY;
+    }
+    (variance / (n - 1) as f64).sqrt()
+}
+
+#[inline]
+fn normal_interval(mean: f64, std_error: f64, z: f64) -> (f64, f64) {
+    let h = z * std_error;
+    ((mean - h).max(0.0), (mean + h).min(1.0))
+}
+
+#[inline]
+fn wilson_interval(n: u64, p: f64, z: f64) -> (f64, f64) {
+    if n == 0 {
+        return (0.0, 1.0);
+    }
+    let n = n as f64;
+    let z2 = z * z;
+    let denom = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denom;
+    let h = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denom;
+    ((center - h).max(0.0), (center + h).min(1.0))
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum EquityError {
     TooManyBoardCards(usize),
+    /// The function needs at least a flop on the board.
+    TooFewBoardCards(usize),
     DuplicateCard(u8),
     CardOutOfRange(u8),
     TooFewPlayers,
     TooManyPlayers,
+    /// A range weight was negative, NaN or infinite.
+    InvalidWeight,
+    /// A range has no combo with positive weight left after removing known cards.
+    EmptyRange,
+    /// Sampling could not find a deal where the ranges do not collide.
+    NoValidDeal,
+    /// Known and dead cards leave too few cards to deal the hands and board.
+    NotEnoughCards,
+    /// A serialized table is truncated, has the wrong header or bad entries.
+    InvalidTable,
+    /// A histogram was asked for zero bins.
+    InvalidBinCount,
+    /// A clustering was asked for zero buckets or more than a bucket map can hold.
+    InvalidBucketCount,
+    /// A percentile was NaN or outside 0.0..=1.0.
+    InvalidPercentile,
 }
 
 #[inline(always)]
@@ -72,10 +290,11 @@ fn add_used(used: &mut u64, id: u8) -> Result<(), EquityError> {
 }
 
 #[inline]
-fn validate_inputs(
+pub(crate) fn validate_inputs(
     hero: &[u8; 2],
     villain: Option<&[u8; 2]>,
     board: &[u8],
+    dead: &[u8],
 ) -> Result<u64, EquityError> {
     if board.len() > 5 {
         return Err(EquityError::TooManyBoardCards(board.len()));
@@ -90,11 +309,39 @@ fn validate_inputs(
     for &c in board {
         add_used(&mut used, c)?;
     }
+    for &c in dead {
+        add_used(&mut used, c)?;
+    }
+    Ok(used)
+}
+
+/// Card mask of a 3-5 card board plus dead cards.
+#[inline]
+pub(crate) fn validate_board(board: &[u8], dead: &[u8]) -> Result<u64, EquityError> {
+    if board.len() < 3 {
+        return Err(EquityError::TooFewBoardCards(board.len()));
+    }
+    if board.len() > 5 {
+        return Err(EquityError::TooManyBoardCards(board.len()));
+    }
+    let mut used = 0u64;
+    for &c in board.iter().chain(dead) {
+        add_used(&mut used, c)?;
+    }
     Ok(used)
 }
 
+/// Fail unless at least `need` cards are left outside `used`.
+#[inline]
+pub(crate) fn check_deck(used: u64, need: usize) -> Result<(), EquityError> {
+    if 52 - (used.count_ones() as usize) < need {
+        return Err(EquityError::NotEnoughCards);
+    }
+    Ok(())
+}
+
 #[inline(always)]
-fn fill_remaining_cards(used: u64, out: &mut [u8; 52]) -> usize {
+pub(crate) fn fill_remaining_cards(used: u64, out: &mut [u8; 52]) -> usize {
     let mut n = 0usize;
     for id in 0u8..52u8 {
         if (used & (1u64 << id)) == 0 {
@@ -145,7 +392,7 @@ pub fn compare_showdown_checked(
     villain: &[u8; 2],
     board: &[u8; 5],
 ) -> Result<Outcome, EquityError> {
-    let _ = validate_inputs(hero, Some(villain), board)?;
+    let _ = validate_inputs(hero, Some(villain), board, &[])?;
     Ok(eval_two_players_unchecked(hero, villain, board))
 }
 
@@ -155,16 +402,16 @@ pub fn compare_showdown_checked(
 
 /// xorshift64 for speed (fine for simulation; not crypto).
 #[derive(Clone)]
-struct XorShift64 {
+pub(crate) struct XorShift64 {
     state: u64,
 }
 impl XorShift64 {
     #[inline(always)]
-    fn new(seed: u64) -> Self {
+    pub(crate) fn new(seed: u64) -> Self {
         Self { state: seed }
     }
     #[inline(always)]
-    fn next_u64(&mut self) -> u64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
         let mut x = self.state;
         x ^= x << 13;
         x ^= x >> 7;
@@ -213,6 +460,10 @@ impl CardSampler52 {
             }
         }
     }
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
 }
 
 #[inline(always)]
@@ -235,6 +486,20 @@ fn sample_distinct_cards(
     Ok(())
 }
 
+/// Seed for shard `shard` of a run seeded with `seed` (splitmix64 mix; never zero,
+/// which would stall xorshift).
+fn shard_seed(seed: u64, shard: u64) -> u64 {
+    let mut z = seed.wrapping_add(shard.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    if z == 0 {
+        0x9E37_79B9_7F4A_7C15
+    } else {
+        z
+    }
+}
+
 /// Monte Carlo equity vs a *known* villain hand.
 /// - `board` length: 0..5
 /// - samples remaining community cards
@@ -245,10 +510,37 @@ pub fn equity_mc_vs_hand_checked(
     iters: u64,
     seed: u64,
 ) -> Result<EquityCounts, EquityError> {
-    let used0 = validate_inputs(hero, Some(villain), board)?;
+    equity_mc_vs_hand_checked_with_dead(hero, villain, board, &[], iters, seed)
+}
+
+/// [`equity_mc_vs_hand_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_mc_vs_hand_checked_with_dead(
+    hero: &[u8; 2],
+    villain: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<EquityCounts, EquityError> {
+    equity_mc_vs_hand_sampled_checked(hero, villain, board, dead, iters, seed, Sampling::Uniform)
+}
+
+/// [`equity_mc_vs_hand_checked_with_dead`] with a choice of runout [`Sampling`].
+pub fn equity_mc_vs_hand_sampled_checked(
+    hero: &[u8; 2],
+    villain: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<EquityCounts, EquityError> {
+    let used0 = validate_inputs(hero, Some(villain), board, dead)?;
     let missing = 5usize.saturating_sub(board.len());
+    check_deck(used0, missing)?;
     let mut counts = EquityCounts::default();
     let mut s = CardSampler52::new(seed);
+    let mut runouts = RunoutSampler::new(sampling, used0, missing, iters, &mut s);
 
     let mut board5 = [0u8; 5];
     for (i, &c) in board.iter().enumerate() {
@@ -258,17 +550,13 @@ pub fn equity_mc_vs_hand_checked(
     let mut fill = [0u8; 5]; // we'll use only first `missing`
     for _ in 0..iters {
         let mut used = used0;
-        sample_distinct_cards(&mut s, &mut used, &mut fill[..missing])?;
+        runouts.next(&mut s, &mut used, &mut fill[..missing]);
 
         for i in 0..missing {
             board5[board.len() + i] = fill[i];
         }
 
-        match eval_two_players_unchecked(hero, villain, &board5) {
-            Outcome::HeroWin => counts.win += 1,
-            Outcome::Tie => counts.tie += 1,
-            Outcome::VillainWin => counts.lose += 1,
-        }
+        bump_counts(&mut counts, eval_two_players_unchecked(hero, villain, &board5));
     }
     Ok(counts)
 }
@@ -282,10 +570,35 @@ pub fn equity_mc_vs_random_checked(
     iters: u64,
     seed: u64,
 ) -> Result<EquityCounts, EquityError> {
-    let used0 = validate_inputs(hero, None, board)?;
+    equity_mc_vs_random_checked_with_dead(hero, board, &[], iters, seed)
+}
+
+/// [`equity_mc_vs_random_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_mc_vs_random_checked_with_dead(
+    hero: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<EquityCounts, EquityError> {
+    equity_mc_vs_random_sampled_checked(hero, board, dead, iters, seed, Sampling::Uniform)
+}
+
+/// [`equity_mc_vs_random_checked_with_dead`] with a choice of runout [`Sampling`].
+pub fn equity_mc_vs_random_sampled_checked(
+    hero: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<EquityCounts, EquityError> {
+    let used0 = validate_inputs(hero, None, board, dead)?;
     let missing = 5usize.saturating_sub(board.len());
+    check_deck(used0, 2 + missing)?;
     let mut counts = EquityCounts::default();
     let mut s = CardSampler52::new(seed);
+    let mut runouts = RunoutSampler::new(sampling, used0, missing, iters, &mut s);
 
     let mut board5 = [0u8; 5];
     for (i, &c) in board.iter().enumerate() {
@@ -298,19 +611,21 @@ pub fn equity_mc_vs_random_checked(
     for _ in 0..iters {
         let mut used = used0;
 
-        // sample villain first, then board runout
-        sample_distinct_cards(&mut s, &mut used, &mut villain)?;
-        sample_distinct_cards(&mut s, &mut used, &mut fill[..missing])?;
+        // Uniform: villain first, then board runout. Other strategies lay out
+        // the runout over the full remaining deck, so it must come first.
+        if runouts.is_uniform() {
+            sample_distinct_cards(&mut s, &mut used, &mut villain)?;
+            runouts.next(&mut s, &mut used, &mut fill[..missing]);
+        } else {
+            runouts.next(&mut s, &mut used, &mut fill[..missing]);
+            sample_distinct_cards(&mut s, &mut used, &mut villain)?;
+        }
 
         for i in 0..missing {
             board5[board.len() + i] = fill[i];
         }
 
-        match eval_two_players_unchecked(hero, &villain, &board5) {
-            Outcome::HeroWin => counts.win += 1,
-            Outcome::Tie => counts.tie += 1,
-            Outcome::VillainWin => counts.lose += 1,
-        }
+        bump_counts(&mut counts, eval_two_players_unchecked(hero, &villain, &board5));
     }
 
     Ok(counts)
@@ -322,17 +637,24 @@ pub fn equity_mc_vs_random_checked(
 
 #[inline(always)]
 fn bump_counts(counts: &mut EquityCounts, out: Outcome) {
+    bump_counts_weighted(counts, out, 1);
+}
+
+/// Record an outcome standing for `w` runouts.
+#[inline(always)]
+fn bump_counts_weighted(counts: &mut EquityCounts, out: Outcome, w: u64) {
     match out {
-        Outcome::HeroWin => counts.win += 1,
-        Outcome::Tie => counts.tie += 1,
-        Outcome::VillainWin => counts.lose += 1,
+        Outcome::HeroWin => counts.win += w,
+        Outcome::Tie => counts.tie += w,
+        Outcome::VillainWin => counts.lose += w,
     }
 }
 
 /// Enumerate all combinations of `missing` cards from `rem[0..m]` and call `f(board5)`.
-/// Implemented as specialized nested loops for speed (missing <= 5).
-#[inline]
-fn enumerate_board_completions(
+/// Implemented as specialized nested loops (missing <= 5). The exact equity functions
+/// go through runout classes instead; this full walk serves callers that track
+/// specific villain cards, and is the reference the classes are tested against.
+pub(crate) fn enumerate_board_completions(
     rem: &[u8],
     known_board: &[u8],
     missing: usize,
@@ -350,17 +672,17 @@ fn enumerate_board_completions(
             f(board5);
         }
         1 => {
-            for i in 0..m {
-                board5[base] = rem[i];
+            for &a in rem {
+                board5[base] = a;
                 f(board5);
             }
         }
         2 => {
             for i in 0..m.saturating_sub(1) {
                 let a = rem[i];
-                for j in (i + 1)..m {
+                for &b in &rem[i + 1..] {
                     board5[base] = a;
-                    board5[base + 1] = rem[j];
+                    board5[base + 1] = b;
                     f(board5);
                 }
             }
@@ -370,10 +692,10 @@ fn enumerate_board_completions(
                 let a = rem[i];
                 for j in (i + 1)..m.saturating_sub(1) {
                     let b = rem[j];
-                    for k in (j + 1)..m {
+                    for &c in &rem[j + 1..] {
                         board5[base] = a;
                         board5[base + 1] = b;
-                        board5[base + 2] = rem[k];
+                        board5[base + 2] = c;
                         f(board5);
                     }
                 }
@@ -386,11 +708,11 @@ fn enumerate_board_completions(
                     let b = rem[j];
                     for k in (j + 1)..m.saturating_sub(1) {
                         let c = rem[k];
-                        for l in (k + 1)..m {
+                        for &d in &rem[k + 1..] {
                             board5[base] = a;
                             board5[base + 1] = b;
                             board5[base + 2] = c;
-                            board5[base + 3] = rem[l];
+                            board5[base + 3] = d;
                             f(board5);
                         }
                     }
@@ -406,12 +728,12 @@ fn enumerate_board_completions(
                         let c = rem[k];
                         for l in (k + 1)..m.saturating_sub(1) {
                             let d = rem[l];
-                            for p in (l + 1)..m {
+                            for &e in &rem[l + 1..] {
                                 board5[base] = a;
                                 board5[base + 1] = b;
                                 board5[base + 2] = c;
                                 board5[base + 3] = d;
-                                board5[base + 4] = rem[p];
+                                board5[base + 4] = e;
                                 f(board5);
                             }
                         }
@@ -426,22 +748,31 @@ fn enumerate_board_completions(
 }
 
 /// Exact equity vs a *known* villain hand by enumerating all remaining board runouts.
+///
+/// Suit-isomorphic runouts are evaluated once and weighted, so the counts equal a
+/// full enumeration at a fraction of the cost.
 pub fn equity_exact_vs_hand_checked(
     hero: &[u8; 2],
     villain: &[u8; 2],
     board: &[u8],
 ) -> Result<EquityCounts, EquityError> {
-    let used0 = validate_inputs(hero, Some(villain), board)?;
-    let missing = 5usize.saturating_sub(board.len());
+    equity_exact_vs_hand_checked_with_dead(hero, villain, board, &[])
+}
 
-    let mut buf = [0u8; 52];
-    let nrem = fill_remaining_cards(used0, &mut buf);
-    let rem = &buf[..nrem];
+/// [`equity_exact_vs_hand_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_exact_vs_hand_checked_with_dead(
+    hero: &[u8; 2],
+    villain: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<EquityCounts, EquityError> {
+    let used0 = validate_inputs(hero, Some(villain), board, dead)?;
+    check_deck(used0, 5 - board.len())?;
 
     let mut counts = EquityCounts::default();
-    enumerate_board_completions(rem, board, missing, |board5| {
-        let out = eval_two_players_unchecked(hero, villain, &board5);
-        bump_counts(&mut counts, out);
+    for_each_runout_class(used0, board, |board5, w| {
+        let out = eval_two_players_unchecked(hero, villain, board5);
+        bump_counts_weighted(&mut counts, out, w);
     });
 
     Ok(counts)
@@ -450,46 +781,114 @@ pub fn equity_exact_vs_hand_checked(
 /// Exact equity vs a *random* villain hand (uniform over remaining villain combos)
 /// AND all remaining board runouts.
 ///
-/// Warning: preflop this can be ~2.1 billion evaluations (still feasible with your speed,
-/// but it will take seconds to minutes depending on hardware).
+/// Boards are the outer loop: hero is evaluated once per (suit-isomorphic) runout,
+/// then every villain holding left over is scored against that result.
 pub fn equity_exact_vs_random_checked(
     hero: &[u8; 2],
     board: &[u8],
 ) -> Result<EquityCounts, EquityError> {
-    let used_hero_board = validate_inputs(hero, None, board)?;
-    let missing = 5usize.saturating_sub(board.len());
+    equity_exact_vs_random_checked_with_dead(hero, board, &[])
+}
 
-    // Remaining cards after hero+known board
-    let mut buf1 = [0u8; 52];
-    let n1 = fill_remaining_cards(used_hero_board, &mut buf1);
-    let rem1 = &buf1[..n1];
+/// [`equity_exact_vs_random_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_exact_vs_random_checked_with_dead(
+    hero: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<EquityCounts, EquityError> {
+    let used = validate_inputs(hero, None, board, dead)?;
+    check_deck(used, 7 - board.len())?;
 
     let mut counts = EquityCounts::default();
+    for_each_runout_class(used, board, |board5, w| {
+        score_random_villains(hero, board5, used, w, &mut counts);
+    });
 
-    // Enumerate villain combos from rem1
-    for i in 0..rem1.len().saturating_sub(1) {
-        let v0 = rem1[i];
-        for j in (i + 1)..rem1.len() {
-            let v1 = rem1[j];
-            let villain = [v0, v1];
-
-            // Build remaining cards after also removing villain
-            let mut used = used_hero_board;
-            used |= 1u64 << v0;
-            used |= 1u64 << v1;
+    Ok(counts)
+}
 
-            let mut buf2 = [0u8; 52];
-            let n2 = fill_remaining_cards(used, &mut buf2);
-            let rem2 = &buf2[..n2];
+/// Score hero against every villain holding from the cards outside `used` and
+/// `board5`, each standing for `w` runouts.
+///
+/// Off the board's flush suit (a suit with 3+ board cards, if any) a villain card
+/// only matters by rank, so holdings are grouped by the rank of each card and
+/// whether it is in the flush suit; one representative per group is evaluated.
+#[inline]
+fn score_random_villains(
+    hero: &[u8; 2],
+    board5: &[u8; 5],
+    used: u64,
+    w: u64,
+    counts: &mut EquityCounts,
+) {
+    let mut bb_board = BitBoard4x13::new();
+    let mut taken = used;
+    let mut per_suit = [0u8; 4];
+    for &c in board5 {
+        bb_board.add_id(c);
+        taken |= 1u64 << c;
+        per_suit[(c / 13) as usize] += 1;
+    }
+    let flush_suit = per_suit.iter().position(|&n| n >= 3);
+
+    let mut b = bb_board;
+    b.add_id(hero[0]);
+    b.add_id(hero[1]);
+    let hero_score = evaluate_u32(&b).0;
+
+    // Card groups: (suits still available as a mask, rank); a flush-suit card is its own group.
+    let mut groups = [(0u8, 0u8); 26];
+    let mut ng = 0;
+    for r in 0..13u8 {
+        let mut off = 0u8;
+        for s in 0..4u8 {
+            if (taken >> (s * 13 + r)) & 1 == 0 {
+                if Some(s as usize) == flush_suit {
+                    groups[ng] = (1 << s, r);
+                    ng += 1;
+                } else {
+                    off |= 1 << s;
+                }
+            }
+        }
+        if off != 0 {
+            groups[ng] = (off, r);
+            ng += 1;
+        }
+    }
 
-            enumerate_board_completions(rem2, board, missing, |board5| {
-                let out = eval_two_players_unchecked(hero, &villain, &board5);
-                bump_counts(&mut counts, out);
-            });
+    let card = |suits: u8, r: u8| suits.trailing_zeros() as u8 * 13 + r;
+    for i in 0..ng {
+        let (si, ri) = groups[i];
+        let ni = si.count_ones() as u64;
+        // Both cards from the same group.
+        if ni >= 2 {
+            let villain = [card(si, ri), card(si & (si - 1), ri)];
+            let out = showdown_vs(hero_score, &bb_board, &villain);
+            bump_counts_weighted(counts, out, w * ni * (ni - 1) / 2);
+        }
+        for &(sj, rj) in &groups[i + 1..ng] {
+            let villain = [card(si, ri), card(sj, rj)];
+            let out = showdown_vs(hero_score, &bb_board, &villain);
+            bump_counts_weighted(counts, out, w * ni * sj.count_ones() as u64);
         }
     }
+}
 
-    Ok(counts)
+/// Hero's showdown result, given hero's score, against `villain` on `bb_board`.
+#[inline(always)]
+fn showdown_vs(hero_score: u32, bb_board: &BitBoard4x13, villain: &[u8; 2]) -> Outcome {
+    let mut b = *bb_board;
+    b.add_id(villain[0]);
+    b.add_id(villain[1]);
+    let v = evaluate_u32(&b).0;
+    if hero_score > v {
+        Outcome::HeroWin
+    } else if hero_score < v {
+        Outcome::VillainWin
+    } else {
+        Outcome::Tie
+    }
 }
 
 // -------------------------
@@ -499,6 +898,28 @@ pub fn equity_exact_vs_random_checked(
 /// Multi-way equity result: one EquityCounts per player.
 pub type MultiWayResult = Vec<EquityCounts>;
 
+/// Raw counts of per-player pot-share results.
+#[inline]
+fn raw_counts(results: Vec<MultiWayCounts>) -> MultiWayResult {
+    results.into_iter().map(|r| r.counts).collect()
+}
+
+/// How often the pot went to 1, 2, ... players, from the per-player results of
+/// one multi-way run: `dist[k]` counts trials won outright (k = 1) or chopped k ways.
+pub fn split_distribution(results: &[MultiWayCounts]) -> [u64; MAX_PLAYERS + 1] {
+    let mut dist = [0u64; MAX_PLAYERS + 1];
+    for r in results {
+        dist[1] += r.win;
+        for (k, &n) in r.split_ways.iter().enumerate().skip(2) {
+            dist[k] += n;
+        }
+    }
+    for (k, d) in dist.iter_mut().enumerate().skip(2) {
+        *d /= k as u64;
+    }
+    dist
+}
+
 /// Determine winners from a slice of scores. Returns indices of winning player(s).
 /// In case of tie, multiple players win.
 #[inline]
@@ -515,28 +936,19 @@ fn find_winners(scores: &[u32]) -> Vec<usize> {
         .collect()
 }
 
-/// Monte Carlo multi-way equity with all known hands.
-/// - `hands` is a slice of 2-9 player hands (each hand is [u8; 2])
-/// - `board` length: 0..5
-/// - Returns one EquityCounts per player
-pub fn equity_mc_multiway_checked(
-    hands: &[&[u8; 2]],
-    board: &[u8],
-    iters: u64,
-    seed: u64,
-) -> Result<MultiWayResult, EquityError> {
-    let n = hands.len();
-    if n < 2 {
+/// Validate 2-9 known hands plus board and dead cards; returns the used-card mask.
+#[inline]
+fn validate_multiway(hands: &[&[u8; 2]], board: &[u8], dead: &[u8]) -> Result<u64, EquityError> {
+    if hands.len() < 2 {
         return Err(EquityError::TooFewPlayers);
     }
-    if n > 9 {
+    if hands.len() > MAX_PLAYERS {
         return Err(EquityError::TooManyPlayers);
     }
     if board.len() > 5 {
         return Err(EquityError::TooManyBoardCards(board.len()));
     }
 
-    // Validate no duplicates
     let mut used: u64 = 0;
     for hand in hands {
         add_used(&mut used, hand[0])?;
@@ -545,10 +957,127 @@ pub fn equity_mc_multiway_checked(
     for &c in board {
         add_used(&mut used, c)?;
     }
+    for &c in dead {
+        add_used(&mut used, c)?;
+    }
+    Ok(used)
+}
+
+/// Score every hand on a complete board.
+#[inline(always)]
+fn score_players<'a>(
+    hands: impl IntoIterator<Item = &'a [u8; 2]>,
+    board5: &[u8; 5],
+    scores: &mut [u32],
+) {
+    let mut bb_board = BitBoard4x13::new();
+    for &c in board5 {
+        bb_board.add_id(c);
+    }
+    for (score, hand) in scores.iter_mut().zip(hands) {
+        let mut b = bb_board;
+        b.add_id(hand[0]);
+        b.add_id(hand[1]);
+        *score = evaluate_u32(&b).0;
+    }
+}
+
+/// Record one showdown: the best score(s) win or split, everyone else loses.
+#[inline]
+fn record_showdown(results: &mut [MultiWayCounts], scores: &[u32]) {
+    record_showdown_weighted(results, scores, 1);
+}
+
+/// Record a showdown standing for `w` runouts.
+#[inline]
+fn record_showdown_weighted(results: &mut [MultiWayCounts], scores: &[u32], w: u64) {
+    let best = scores.iter().copied().max().unwrap_or(0);
+    let nwin = scores.iter().filter(|&&s| s == best).count();
+    for (r, &s) in results.iter_mut().zip(scores) {
+        if s != best {
+            r.counts.lose += w;
+        } else if nwin == 1 {
+            r.counts.win += w;
+        } else {
+            r.counts.tie += w;
+            r.split_ways[nwin] += w;
+        }
+    }
+}
+
+/// Monte Carlo multi-way equity with all known hands.
+/// - `hands` is a slice of 2-9 player hands (each hand is [u8; 2])
+/// - `board` length: 0..5
+/// - Returns one EquityCounts per player
+pub fn equity_mc_multiway_checked(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<MultiWayResult, EquityError> {
+    equity_mc_multiway_checked_with_dead(hands, board, &[], iters, seed)
+}
+
+/// [`equity_mc_multiway_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_mc_multiway_checked_with_dead(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<MultiWayResult, EquityError> {
+    equity_mc_multiway_sampled_checked(hands, board, dead, iters, seed, Sampling::Uniform)
+}
+
+/// [`equity_mc_multiway_checked_with_dead`] with a choice of runout [`Sampling`].
+pub fn equity_mc_multiway_sampled_checked(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<MultiWayResult, EquityError> {
+    mc_multiway(hands, board, dead, iters, seed, sampling).map(raw_counts)
+}
+
+/// [`equity_mc_multiway_checked`] with each player's chops credited as exact pot shares.
+pub fn equity_mc_multiway_pot_share_checked(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    equity_mc_multiway_pot_share_checked_with_dead(hands, board, &[], iters, seed)
+}
+
+/// [`equity_mc_multiway_pot_share_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_mc_multiway_pot_share_checked_with_dead(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    mc_multiway(hands, board, dead, iters, seed, Sampling::Uniform)
+}
+
+fn mc_multiway(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    let n = hands.len();
+    let used = validate_multiway(hands, board, dead)?;
 
     let missing = 5usize.saturating_sub(board.len());
-    let mut results = vec![EquityCounts::default(); n];
+    check_deck(used, missing)?;
+    let mut results = vec![MultiWayCounts::default(); n];
     let mut s = CardSampler52::new(seed);
+    let mut runouts = RunoutSampler::new(sampling, used, missing, iters, &mut s);
 
     let mut board5 = [0u8; 5];
     for (i, &c) in board.iter().enumerate() {
@@ -556,56 +1085,22 @@ pub fn equity_mc_multiway_checked(
     }
 
     let mut fill = [0u8; 5];
-    let mut boards = vec![BitBoard4x13::new(); n];
     let mut scores = vec![0u32; n];
 
     for _ in 0..iters {
         let mut used_iter = used;
-        sample_distinct_cards(&mut s, &mut used_iter, &mut fill[..missing])?;
+        runouts.next(&mut s, &mut used_iter, &mut fill[..missing]);
 
         for i in 0..missing {
             board5[board.len() + i] = fill[i];
         }
 
-        // Build board base
-        let mut bb_board = BitBoard4x13::new();
-        for &c in &board5 {
-            bb_board.add_id(c);
-        }
+        score_players(hands.iter().copied(), &board5, &mut scores);
+        record_showdown(&mut results, &scores);
+    }
 
-        // Evaluate each player
-        for (i, hand) in hands.iter().enumerate() {
-            boards[i] = bb_board;
-            boards[i].add_id(hand[0]);
-            boards[i].add_id(hand[1]);
-            scores[i] = evaluate_u32(&boards[i]).0;
-        }
-
-        // Find winner(s)
-        let winners = find_winners(&scores);
-        if winners.len() == 1 {
-            // Sole winner
-            results[winners[0]].win += 1;
-            for i in 0..n {
-                if i != winners[0] {
-                    results[i].lose += 1;
-                }
-            }
-        } else {
-            // Tie among winners
-            for &w in &winners {
-                results[w].tie += 1;
-            }
-            for i in 0..n {
-                if !winners.contains(&i) {
-                    results[i].lose += 1;
-                }
-            }
-        }
-    }
-
-    Ok(results)
-}
+    Ok(results)
+}
 
 /// Monte Carlo equity for hero vs (N-1) random villains.
 /// - `hero` is the known hand
@@ -619,10 +1114,79 @@ pub fn equity_mc_vs_random_multiway_checked(
     iters: u64,
     seed: u64,
 ) -> Result<EquityCounts, EquityError> {
+    equity_mc_vs_random_multiway_checked_with_dead(hero, num_villains, board, &[], iters, seed)
+}
+
+/// [`equity_mc_vs_random_multiway_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_mc_vs_random_multiway_checked_with_dead(
+    hero: &[u8; 2],
+    num_villains: usize,
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<EquityCounts, EquityError> {
+    equity_mc_vs_random_multiway_sampled_checked(
+        hero,
+        num_villains,
+        board,
+        dead,
+        iters,
+        seed,
+        Sampling::Uniform,
+    )
+}
+
+/// [`equity_mc_vs_random_multiway_checked_with_dead`] with a choice of runout [`Sampling`].
+pub fn equity_mc_vs_random_multiway_sampled_checked(
+    hero: &[u8; 2],
+    num_villains: usize,
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<EquityCounts, EquityError> {
+    mc_vs_random_multiway(hero, num_villains, board, dead, iters, seed, sampling).map(|c| c.counts)
+}
+
+/// [`equity_mc_vs_random_multiway_checked`] with hero's chops credited as exact pot shares.
+pub fn equity_mc_vs_random_multiway_pot_share_checked(
+    hero: &[u8; 2],
+    num_villains: usize,
+    board: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<MultiWayCounts, EquityError> {
+    equity_mc_vs_random_multiway_pot_share_checked_with_dead(hero, num_villains, board, &[], iters, seed)
+}
+
+/// [`equity_mc_vs_random_multiway_pot_share_checked`] with `dead` cards (folded or exposed)
+/// removed from the deck.
+pub fn equity_mc_vs_random_multiway_pot_share_checked_with_dead(
+    hero: &[u8; 2],
+    num_villains: usize,
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<MultiWayCounts, EquityError> {
+    mc_vs_random_multiway(hero, num_villains, board, dead, iters, seed, Sampling::Uniform)
+}
+
+fn mc_vs_random_multiway(
+    hero: &[u8; 2],
+    num_villains: usize,
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<MultiWayCounts, EquityError> {
     if num_villains < 1 {
         return Err(EquityError::TooFewPlayers);
     }
-    if num_villains > 8 {
+    if num_villains >= MAX_PLAYERS {
         return Err(EquityError::TooManyPlayers);
     }
     if board.len() > 5 {
@@ -635,10 +1199,15 @@ pub fn equity_mc_vs_random_multiway_checked(
     for &c in board {
         add_used(&mut used0, c)?;
     }
+    for &c in dead {
+        add_used(&mut used0, c)?;
+    }
 
     let missing = 5usize.saturating_sub(board.len());
-    let mut counts = EquityCounts::default();
+    check_deck(used0, 2 * num_villains + missing)?;
+    let mut counts = MultiWayCounts::default();
     let mut s = CardSampler52::new(seed);
+    let mut runouts = RunoutSampler::new(sampling, used0, missing, iters, &mut s);
 
     let mut board5 = [0u8; 5];
     for (i, &c) in board.iter().enumerate() {
@@ -653,13 +1222,16 @@ pub fn equity_mc_vs_random_multiway_checked(
     for _ in 0..iters {
         let mut used = used0;
 
-        // Sample villain hands
+        // Uniform samples villain hands first; other strategies need the runout first.
+        if !runouts.is_uniform() {
+            runouts.next(&mut s, &mut used, &mut fill[..missing]);
+        }
         for v in &mut villains {
             sample_distinct_cards(&mut s, &mut used, v)?;
         }
-
-        // Sample remaining board
-        sample_distinct_cards(&mut s, &mut used, &mut fill[..missing])?;
+        if runouts.is_uniform() {
+            runouts.next(&mut s, &mut used, &mut fill[..missing]);
+        }
 
         for i in 0..missing {
             board5[board.len() + i] = fill[i];
@@ -689,16 +1261,17 @@ pub fn equity_mc_vs_random_multiway_checked(
         let winners = find_winners(&scores);
         if winners.len() == 1 {
             if winners[0] == 0 {
-                counts.win += 1;
+                counts.counts.win += 1;
             } else {
-                counts.lose += 1;
+                counts.counts.lose += 1;
             }
         } else {
             // Tie
             if winners.contains(&0) {
-                counts.tie += 1;
+                counts.counts.tie += 1;
+                counts.split_ways[winners.len()] += 1;
             } else {
-                counts.lose += 1;
+                counts.counts.lose += 1;
             }
         }
     }
@@ -706,77 +1279,346 @@ pub fn equity_mc_vs_random_multiway_checked(
     Ok(counts)
 }
 
-/// Exact multi-way equity with all known hands by enumerating all board runouts.
-/// - `hands` is a slice of 2-9 player hands
-/// - `board` length: 0..5
-/// - Warning: can be very slow for preflop scenarios with many players
-pub fn equity_exact_multiway_checked(
-    hands: &[&[u8; 2]],
+/// A villain seat for [`equity_mc_ranges_multiway_checked`].
+#[derive(Copy, Clone, Debug)]
+pub enum Seat<'a> {
+    /// A known hand.
+    Hand([u8; 2]),
+    /// A weighted range; combos are dealt proportionally to their weight.
+    Range(&'a Range),
+    /// A uniformly random hand from the remaining deck.
+    Random,
+}
+
+/// Give up after this many consecutive colliding range deals.
+const MAX_DEAL_ATTEMPTS: u32 = 1_000_000;
+
+/// Walker/Vose alias table over the combos of one range seat.
+struct RangeSampler {
+    seat: usize,
+    hands: Vec<[u8; 2]>,
+    masks: Vec<u64>,
+    /// Acceptance threshold scaled to 2^32.
+    prob: Vec<u32>,
+    alias: Vec<u32>,
+}
+
+impl RangeSampler {
+    /// Build from the combos of `range` that do not touch `dead`.
+    fn new(seat: usize, range: &Range, dead: u64) -> Result<Self, EquityError> {
+        let mut hands = Vec::with_capacity(range.len());
+        let mut weights = Vec::with_capacity(range.len());
+        for c in range.combos() {
+            let mask = (1u64 << c.hand[0]) | (1u64 << c.hand[1]);
+            if c.weight > 0.0 && (mask & dead) == 0 {
+                hands.push(c.hand);
+                weights.push(c.weight);
+            }
+        }
+        let n = hands.len();
+        if n == 0 {
+            return Err(EquityError::EmptyRange);
+        }
+
+        let total: f64 = weights.iter().sum();
+        let mut scaled: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let mut prob = vec![u32::MAX; n];
+        let mut alias: Vec<u32> = (0..n as u32).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| scaled[i] < 1.0);
+        while let (Some(s), Some(&l)) = (small.pop(), large.last()) {
+            prob[s] = (scaled[s] * 4_294_967_296.0) as u32;
+            alias[s] = l as u32;
+            scaled[l] -= 1.0 - scaled[s];
+            if scaled[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+
+        let masks = hands
+            .iter()
+            .map(|h| (1u64 << h[0]) | (1u64 << h[1]))
+            .collect();
+        Ok(Self {
+            seat,
+            hands,
+            masks,
+            prob,
+            alias,
+        })
+    }
+
+    #[inline(always)]
+    fn sample(&self, s: &mut CardSampler52) -> usize {
+        let r = s.next_u64();
+        let i = (((r >> 32) * self.hands.len() as u64) >> 32) as usize;
+        if (r as u32) < self.prob[i] {
+            i
+        } else {
+            self.alias[i] as usize
+        }
+    }
+}
+
+/// Deals the non-fixed villain seats: ranges jointly by whole-deal rejection, then
+/// random seats from what is left. Seat `i` is written to `hands[i + 1]`.
+struct SeatDealer {
+    samplers: Vec<RangeSampler>,
+    random_seats: Vec<usize>,
+}
+
+impl SeatDealer {
+    /// Range combos touching `used0` are dropped up front.
+    fn new(villains: &[Seat], used0: u64) -> Result<Self, EquityError> {
+        let mut samplers = Vec::new();
+        let mut random_seats = Vec::new();
+        for (i, seat) in villains.iter().enumerate() {
+            match seat {
+                Seat::Hand(_) => {}
+                Seat::Range(r) => samplers.push(RangeSampler::new(i + 1, r, used0)?),
+                Seat::Random => random_seats.push(i + 1),
+            }
+        }
+        samplers.sort_by_key(|rs| rs.hands.len());
+        Ok(Self {
+            samplers,
+            random_seats,
+        })
+    }
+
+    /// Cards dealt to the range and random seats.
+    #[inline]
+    fn cards(&self) -> usize {
+        2 * (self.samplers.len() + self.random_seats.len())
+    }
+
+    /// Deal one set of villain hands; returns `used0` plus the dealt cards.
+    #[inline]
+    fn deal(&self, s: &mut CardSampler52, used0: u64, hands: &mut [[u8; 2]]) -> Result<u64, EquityError> {
+        // Deal all range seats jointly; restart on any collision.
+        let mut used;
+        let mut attempts = 0u32;
+        'deal: loop {
+            used = used0;
+            for rs in &self.samplers {
+                let k = rs.sample(s);
+                if (used & rs.masks[k]) != 0 {
+                    attempts += 1;
+                    if attempts >= MAX_DEAL_ATTEMPTS {
+                        return Err(EquityError::NoValidDeal);
+                    }
+                    continue 'deal;
+                }
+                used |= rs.masks[k];
+                hands[rs.seat] = rs.hands[k];
+            }
+            break;
+        }
+
+        for &seat in &self.random_seats {
+            sample_distinct_cards(s, &mut used, &mut hands[seat])?;
+        }
+        Ok(used)
+    }
+}
+
+/// Hero and known villain hands in seat order (range and random seats hold hero's
+/// hand as a placeholder), and the mask of every known card.
+fn validate_seats(
+    hero: &[u8; 2],
+    villains: &[Seat],
     board: &[u8],
-) -> Result<MultiWayResult, EquityError> {
-    let n = hands.len();
-    if n < 2 {
+    dead: &[u8],
+) -> Result<(Vec<[u8; 2]>, u64), EquityError> {
+    if villains.is_empty() {
         return Err(EquityError::TooFewPlayers);
     }
-    if n > 9 {
+    if villains.len() >= MAX_PLAYERS {
         return Err(EquityError::TooManyPlayers);
     }
     if board.len() > 5 {
         return Err(EquityError::TooManyBoardCards(board.len()));
     }
 
-    let mut used: u64 = 0;
-    for hand in hands {
-        add_used(&mut used, hand[0])?;
-        add_used(&mut used, hand[1])?;
+    let mut hands = vec![*hero; villains.len() + 1];
+    let mut used0: u64 = 0;
+    add_used(&mut used0, hero[0])?;
+    add_used(&mut used0, hero[1])?;
+    for (i, seat) in villains.iter().enumerate() {
+        if let Seat::Hand(h) = seat {
+            add_used(&mut used0, h[0])?;
+            add_used(&mut used0, h[1])?;
+            hands[i + 1] = *h;
+        }
     }
     for &c in board {
-        add_used(&mut used, c)?;
+        add_used(&mut used0, c)?;
+    }
+    for &c in dead {
+        add_used(&mut used0, c)?;
     }
+    Ok((hands, used0))
+}
+
+/// Monte Carlo multi-way equity for a known hero hand vs villains that each hold
+/// a known hand, a weighted range, or a random hand.
+/// - `villains` is 1..8 seats (total players = villains.len() + 1)
+/// - `board` length: 0..5
+/// - Returns one EquityCounts per player: hero first, then villains in seat order
+///
+/// Range deals that collide with each other are rejected as a whole, so the joint
+/// distribution of villain hands is exact. Ranges are pre-filtered against the
+/// known cards and drawn narrowest-first, so overlapping narrow ranges fail fast.
+pub fn equity_mc_ranges_multiway_checked(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<MultiWayResult, EquityError> {
+    equity_mc_ranges_multiway_checked_with_dead(hero, villains, board, &[], iters, seed)
+}
+
+/// [`equity_mc_ranges_multiway_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_mc_ranges_multiway_checked_with_dead(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<MultiWayResult, EquityError> {
+    equity_mc_ranges_multiway_sampled_checked(hero, villains, board, dead, iters, seed, Sampling::Uniform)
+}
+
+/// [`equity_mc_ranges_multiway_checked_with_dead`] with a choice of runout [`Sampling`].
+/// Runouts are drawn after the seats are dealt, as positions among the cards left,
+/// so the strategies stay unbiased whatever the ranges remove.
+pub fn equity_mc_ranges_multiway_sampled_checked(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<MultiWayResult, EquityError> {
+    mc_ranges_multiway(hero, villains, board, dead, iters, seed, sampling).map(raw_counts)
+}
+
+/// [`equity_mc_ranges_multiway_checked`] with each player's chops credited as exact pot shares.
+pub fn equity_mc_ranges_multiway_pot_share_checked(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    equity_mc_ranges_multiway_pot_share_checked_with_dead(hero, villains, board, &[], iters, seed)
+}
+
+/// [`equity_mc_ranges_multiway_pot_share_checked`] with `dead` cards (folded or exposed)
+/// removed from the deck.
+pub fn equity_mc_ranges_multiway_pot_share_checked_with_dead(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    mc_ranges_multiway(hero, villains, board, dead, iters, seed, Sampling::Uniform)
+}
+
+fn mc_ranges_multiway(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    let n = villains.len() + 1;
+    let (mut hands, used0) = validate_seats(hero, villains, board, dead)?;
+    let dealer = SeatDealer::new(villains, used0)?;
 
     let missing = 5usize.saturating_sub(board.len());
-    let mut buf = [0u8; 52];
-    let nrem = fill_remaining_cards(used, &mut buf);
-    let rem = &buf[..nrem];
+    check_deck(used0, dealer.cards() + missing)?;
+    let mut results = vec![MultiWayCounts::default(); n];
+    let mut s = CardSampler52::new(seed);
+    let live = 52 - used0.count_ones() as usize - dealer.cards();
+    let mut runouts = RunoutSampler::positional(sampling, live, missing, iters, &mut s);
 
-    let mut results = vec![EquityCounts::default(); n];
-    let mut boards = vec![BitBoard4x13::new(); n];
+    let mut board5 = [0u8; 5];
+    for (i, &c) in board.iter().enumerate() {
+        board5[i] = c;
+    }
+
+    let mut fill = [0u8; 5];
     let mut scores = vec![0u32; n];
 
-    enumerate_board_completions(rem, board, missing, |board5| {
-        // Build board base
-        let mut bb_board = BitBoard4x13::new();
-        for &c in &board5 {
-            bb_board.add_id(c);
-        }
+    for _ in 0..iters {
+        let mut used = dealer.deal(&mut s, used0, &mut hands)?;
+        runouts.next(&mut s, &mut used, &mut fill[..missing]);
 
-        // Evaluate each player
-        for (i, hand) in hands.iter().enumerate() {
-            boards[i] = bb_board;
-            boards[i].add_id(hand[0]);
-            boards[i].add_id(hand[1]);
-            scores[i] = evaluate_u32(&boards[i]).0;
+        for i in 0..missing {
+            board5[board.len() + i] = fill[i];
         }
 
-        // Find winner(s)
-        let winners = find_winners(&scores);
-        if winners.len() == 1 {
-            results[winners[0]].win += 1;
-            for i in 0..n {
-                if i != winners[0] {
-                    results[i].lose += 1;
-                }
-            }
-        } else {
-            for &w in &winners {
-                results[w].tie += 1;
-            }
-            for i in 0..n {
-                if !winners.contains(&i) {
-                    results[i].lose += 1;
-                }
-            }
-        }
+        score_players(&hands, &board5, &mut scores);
+        record_showdown(&mut results, &scores);
+    }
+
+    Ok(results)
+}
+
+/// Exact multi-way equity with all known hands by enumerating all board runouts.
+/// - `hands` is a slice of 2-9 player hands
+/// - `board` length: 0..5
+/// - Suit-isomorphic runouts are evaluated once and weighted (counts are unchanged)
+pub fn equity_exact_multiway_checked(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+) -> Result<MultiWayResult, EquityError> {
+    equity_exact_multiway_checked_with_dead(hands, board, &[])
+}
+
+/// [`equity_exact_multiway_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_exact_multiway_checked_with_dead(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<MultiWayResult, EquityError> {
+    equity_exact_multiway_pot_share_checked_with_dead(hands, board, dead).map(raw_counts)
+}
+
+/// [`equity_exact_multiway_checked`] with each player's chops credited as exact pot shares.
+pub fn equity_exact_multiway_pot_share_checked(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    equity_exact_multiway_pot_share_checked_with_dead(hands, board, &[])
+}
+
+/// [`equity_exact_multiway_pot_share_checked`] with `dead` cards (folded or exposed) removed
+/// from the deck.
+pub fn equity_exact_multiway_pot_share_checked_with_dead(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    let n = hands.len();
+    let used = validate_multiway(hands, board, dead)?;
+    check_deck(used, 5 - board.len())?;
+
+    let mut results = vec![MultiWayCounts::default(); n];
+    let mut scores = vec![0u32; n];
+
+    for_each_runout_class(used, board, |board5, w| {
+        score_players(hands.iter().copied(), board5, &mut scores);
+        record_showdown_weighted(&mut results, &scores, w);
     });
 
     Ok(results)
@@ -805,6 +1647,47 @@ mod tests {
         assert_eq!(e.total(), 1);
     }
 
+    #[test]
+    fn exact_vs_random_matches_villain_outer_enumeration() {
+        // Villain combos outside, every runout inside: the straightforward definition.
+        fn reference(hero: &[u8; 2], board: &[u8], dead: &[u8]) -> EquityCounts {
+            let used = validate_inputs(hero, None, board, dead).unwrap();
+            let mut buf = [0u8; 52];
+            let n = fill_remaining_cards(used, &mut buf);
+            let mut counts = EquityCounts::default();
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    let villain = [buf[i], buf[j]];
+                    let mut rem = [0u8; 52];
+                    let m = fill_remaining_cards(used | (1u64 << buf[i]) | (1u64 << buf[j]), &mut rem);
+                    enumerate_board_completions(&rem[..m], board, 5 - board.len(), |board5| {
+                        bump_counts(&mut counts, eval_two_players_unchecked(hero, &villain, &board5));
+                    });
+                }
+            }
+            counts
+        }
+
+        let cases: [(&[u8; 2], &[u8], &[u8]); 4] = [
+            (&[12, 25], &[0, 14, 28, 42, 7], &[]),
+            (&[51, 38], &[40, 41, 3, 16], &[7]),
+            (&[39, 0], &[40, 41, 42, 43], &[]),
+            (&[5, 18], &[1, 14, 27, 44], &[2, 15]),
+        ];
+        for (hero, board, dead) in cases {
+            assert_eq!(
+                equity_exact_vs_random_checked_with_dead(hero, board, dead).unwrap(),
+                reference(hero, board, dead),
+                "board {:?}",
+                board
+            );
+        }
+
+        // Flop counts from the previous villain-outer implementation.
+        let e = equity_exact_vs_random_checked(&[12, 25], &[0, 14, 28]).unwrap();
+        assert_eq!((e.win, e.tie, e.lose), (863_330, 32_466, 174_394));
+    }
+
     #[test]
     fn mc_counts_sum() {
         let hero = [0, 1];
@@ -835,6 +1718,57 @@ mod tests {
         assert_eq!(results[2].tie, 1);
     }
 
+    #[test]
+    fn multiway_pot_share_splits_three_ways() {
+        let h1 = [0, 1];
+        let h2 = [13, 14];
+        let h3 = [26, 27];
+        let board = [51, 50, 49, 48, 47];
+
+        let results = equity_exact_multiway_pot_share_checked(&[&h1, &h2, &h3], &board).unwrap();
+        for r in &results {
+            assert_eq!(r.counts, EquityCounts { win: 0, tie: 1, lose: 0 });
+            assert_eq!(r.split_ways[3], 1);
+            assert!((r.equity() - 1.0 / 3.0).abs() < 1e-12);
+        }
+        assert_eq!(split_distribution(&results)[3], 1);
+    }
+
+    #[test]
+    fn multiway_pot_shares_sum_to_one() {
+        let h1 = [12, 11]; // Ac Kc
+        let h2 = [25, 24]; // Ad Kd
+        let h3 = [38, 37]; // Ah Kh
+        let board = [0, 14, 29]; // 2c 3d 5h
+
+        let results = equity_exact_multiway_pot_share_checked(&[&h1, &h2, &h3], &board).unwrap();
+        let total: f64 = results.iter().map(|r| r.equity()).sum();
+        assert!((total - 1.0).abs() < 1e-9, "sum of equities: {}", total);
+
+        let dist = split_distribution(&results);
+        assert_eq!(dist.iter().sum::<u64>(), results[0].total());
+        assert!(dist[3] > 0);
+    }
+
+    #[test]
+    fn equity_counts_legacy_ties_are_half() {
+        let c = EquityCounts {
+            win: 1,
+            tie: 2,
+            lose: 1,
+        };
+        assert_eq!(c.equity(), 0.5);
+
+        // The same ties as three-way chops.
+        let mut m = MultiWayCounts {
+            counts: c,
+            ..Default::default()
+        };
+        m.split_ways[3] = 2;
+        assert_eq!(m.win, 1);
+        assert!((m.equity() - (1.0 + 2.0 / 3.0) / 4.0).abs() < 1e-12);
+    }
+
     #[test]
     fn multiway_mc_counts_correct() {
         let h1 = [0, 1];
@@ -894,6 +1828,278 @@ mod tests {
         assert!(diff < 0.05, "Exact: {}, MC: {}, diff: {}", eq_exact, eq_mc, diff);
     }
 
+    #[test]
+    fn ranges_multiway_matches_exact_for_known_hands() {
+        let hero = [12, 25]; // Ac Ad
+        let v1 = [11, 24]; // Kc Kd
+        let v2 = [10, 23]; // Qc Qd
+        let board = [0, 14, 28];
+
+        let exact = equity_exact_multiway_checked(&[&hero, &v1, &v2], &board).unwrap();
+        let mut r2 = Range::new();
+        r2.add(v2, 1.0).unwrap();
+        let mc = equity_mc_ranges_multiway_checked(
+            &hero,
+            &[Seat::Hand(v1), Seat::Range(&r2)],
+            &board,
+            50_000,
+            7,
+        )
+        .unwrap();
+
+        assert_eq!(mc.len(), 3);
+        for (e, m) in exact.iter().zip(&mc) {
+            assert_eq!(m.total(), 50_000);
+            assert!((e.equity() - m.equity()).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn ranges_multiway_overlapping_narrow_ranges() {
+        // Both villains hold aces; hero holds one ace, so each deal splits the other three.
+        let hero = [51, 50]; // As Ks
+        let mut aces = Range::new();
+        for (a, b) in [(12, 25), (12, 38), (25, 38)] {
+            aces.add([a, b], 1.0).unwrap();
+        }
+        let res = equity_mc_ranges_multiway_checked(
+            &hero,
+            &[Seat::Range(&aces), Seat::Range(&aces), Seat::Random],
+            &[],
+            2_000,
+            3,
+        );
+        // Two disjoint pairs from three aces is impossible.
+        assert_eq!(res, Err(EquityError::NoValidDeal));
+
+        let mut kings = Range::new();
+        for (a, b) in [(11, 24), (11, 37), (24, 37)] {
+            kings.add([a, b], 1.0).unwrap();
+        }
+        let res = equity_mc_ranges_multiway_checked(
+            &hero,
+            &[Seat::Range(&aces), Seat::Range(&kings), Seat::Random],
+            &[],
+            2_000,
+            3,
+        )
+        .unwrap();
+        assert_eq!(res.len(), 4);
+        assert!(res.iter().all(|r| r.total() == 2_000));
+        // Aces beat kings most of the time.
+        assert!(res[1].equity() > res[2].equity());
+    }
+
+    #[test]
+    fn ranges_multiway_errors() {
+        let hero = [0, 1];
+        let mut blocked = Range::new();
+        blocked.add([0, 5], 1.0).unwrap();
+        let res = equity_mc_ranges_multiway_checked(&hero, &[Seat::Range(&blocked)], &[], 10, 0);
+        assert_eq!(res, Err(EquityError::EmptyRange));
+
+        let res = equity_mc_ranges_multiway_checked(&hero, &[], &[], 10, 0);
+        assert_eq!(res, Err(EquityError::TooFewPlayers));
+
+        let res = equity_mc_ranges_multiway_checked(&hero, &[Seat::Hand([1, 2])], &[], 10, 0);
+        assert_eq!(res, Err(EquityError::DuplicateCard(1)));
+    }
+
+    #[test]
+    fn dead_cards_are_removed_from_deck() {
+        let hero = [0, 1];
+        let vill = [2, 3];
+        let turn = [4, 5, 6, 7];
+
+        // 44 river cards remain; kill three of them.
+        let e = equity_exact_vs_hand_checked_with_dead(&hero, &vill, &turn, &[20, 30, 40]).unwrap();
+        assert_eq!(e.total(), 41);
+
+        // Leave a single river card live: MC must always deal it.
+        let dead: Vec<u8> = (8..51).collect();
+        let exact = equity_exact_vs_hand_checked_with_dead(&hero, &vill, &turn, &dead).unwrap();
+        assert_eq!(exact.total(), 1);
+        let mc = equity_mc_vs_hand_checked_with_dead(&hero, &vill, &turn, &dead, 1_000, 5).unwrap();
+        assert_eq!(mc.win, exact.win * 1_000);
+        assert_eq!(mc.tie, exact.tie * 1_000);
+
+        let exact = equity_exact_multiway_checked_with_dead(&[&hero, &vill], &turn, &dead).unwrap();
+        assert_eq!(exact[0].total(), 1);
+    }
+
+    #[test]
+    fn too_few_live_cards_is_an_error() {
+        let hero = [0, 1];
+        let vill = [2, 3];
+        let err = EquityError::NotEnoughCards;
+        // Two live cards cannot complete a preflop board.
+        let dead: Vec<u8> = (4..50).collect();
+        let res = equity_mc_vs_hand_checked_with_dead(&hero, &vill, &[], &dead, 10, 1);
+        assert_eq!(res, Err(err.clone()));
+        let res = equity_exact_vs_hand_checked_with_dead(&hero, &vill, &[], &dead);
+        assert_eq!(res, Err(err.clone()));
+        let res = equity_exact_multiway_checked_with_dead(&[&hero, &vill], &[], &dead);
+        assert_eq!(res, Err(err.clone()));
+
+        // One live river card is enough against a known hand, not against a random one.
+        let turn = [4, 5, 6, 7];
+        let dead: Vec<u8> = (8..51).collect();
+        assert!(equity_mc_vs_hand_checked_with_dead(&hero, &vill, &turn, &dead, 10, 1).is_ok());
+        let dead: Vec<u8> = (2..4).chain(8..51).collect();
+        let res = equity_mc_vs_random_checked_with_dead(&hero, &turn, &dead, 10, 1);
+        assert_eq!(res, Err(err.clone()));
+        let res = equity_exact_vs_random_checked_with_dead(&hero, &turn, &dead);
+        assert_eq!(res, Err(err.clone()));
+
+        // Five live cards: two random villains and a river, but not three.
+        let dead: Vec<u8> = (10..51).collect();
+        assert!(equity_mc_vs_random_multiway_checked_with_dead(&hero, 2, &turn, &dead, 10, 1).is_ok());
+        let res = equity_mc_vs_random_multiway_checked_with_dead(&hero, 3, &turn, &dead, 10, 1);
+        assert_eq!(res, Err(err.clone()));
+        let seats = [Seat::Random, Seat::Random, Seat::Random];
+        let res = equity_mc_ranges_multiway_checked_with_dead(&hero, &seats, &turn, &dead, 10, 1);
+        assert_eq!(res, Err(err));
+    }
+
+    #[test]
+    fn dead_cards_are_validated() {
+        let hero = [0, 1];
+        let vill = [2, 3];
+        let res = equity_exact_vs_hand_checked_with_dead(&hero, &vill, &[], &[1]);
+        assert_eq!(res, Err(EquityError::DuplicateCard(1)));
+        let res = equity_mc_vs_random_checked_with_dead(&hero, &[], &[9, 9], 10, 0);
+        assert_eq!(res, Err(EquityError::DuplicateCard(9)));
+        let res = equity_mc_multiway_checked_with_dead(&[&hero, &vill], &[], &[52], 10, 0);
+        assert_eq!(res, Err(EquityError::CardOutOfRange(52)));
+        let res = equity_mc_ranges_multiway_checked_with_dead(&hero, &[Seat::Random], &[4], &[4], 10, 0);
+        assert_eq!(res, Err(EquityError::DuplicateCard(4)));
+    }
+
+    #[test]
+    fn std_error_and_intervals() {
+        let c = EquityCounts {
+            win: 600,
+            lose: 400,
+            ..Default::default()
+        };
+        // Bernoulli(0.6): sd = sqrt(0.24)
+        assert!((c.variance() - 0.24).abs() < 1e-12);
+        assert!((c.std_error() - (0.24f64 / 999.0).sqrt()).abs() < 1e-12);
+        let (lo, hi) = c.confidence_interval(1.96);
+        assert!(lo < 0.6 && hi > 0.6);
+        let (wlo, whi) = c.wilson_interval(1.96);
+        assert!(wlo < 0.6 && whi > 0.6);
+        assert!((whi - wlo - (hi - lo)).abs() < 0.002);
+
+        // Ties lower the variance relative to a proportion with the same mean.
+        let t = EquityCounts {
+            tie: 1000,
+            ..Default::default()
+        };
+        assert_eq!(t.variance(), 0.0);
+
+        // All wins: normal interval collapses, Wilson does not.
+        let w = EquityCounts {
+            win: 100,
+            ..Default::default()
+        };
+        assert_eq!(w.confidence_interval(1.96), (1.0, 1.0));
+        assert!(w.wilson_interval(1.96).0 < 0.99);
+        assert_eq!(EquityCounts::default().std_error(), f64::INFINITY);
+    }
+
+    #[test]
+    fn sampling_strategies_are_unbiased() {
+        let hero = [12, 25];
+        let vill = [11, 24];
+        let flop = [0, 14, 28];
+        let exact = equity_exact_vs_hand_checked(&hero, &vill, &flop).unwrap().equity();
+
+        for kind in [Sampling::Stratified, Sampling::Systematic, Sampling::QuasiRandom] {
+            let mut mean = 0.0;
+            let mut sq = 0.0;
+            for seed in 1..=40 {
+                // Two full cycles of the 1035 turn/river strata.
+                let e = equity_mc_vs_hand_sampled_checked(&hero, &vill, &flop, &[], 2070, seed, kind)
+                    .unwrap()
+                    .equity();
+                mean += e / 40.0;
+                sq += (e - exact) * (e - exact) / 40.0;
+            }
+            assert!((mean - exact).abs() < 0.005, "{:?}: {} vs {}", kind, mean, exact);
+            // Uniform sampling would give an RMSE around 0.006 here.
+            assert!(sq.sqrt() < 0.003, "{:?}: rmse {}", kind, sq.sqrt());
+        }
+    }
+
+    #[test]
+    fn sampled_variants_count_every_iteration() {
+        let hero = [12, 25];
+        for kind in [Sampling::Stratified, Sampling::Systematic, Sampling::QuasiRandom] {
+            let c = equity_mc_vs_random_sampled_checked(&hero, &[], &[7], 3_000, 1, kind).unwrap();
+            assert_eq!(c.total(), 3_000);
+            let c = equity_mc_vs_random_multiway_sampled_checked(&hero, 3, &[0, 1, 2], &[], 3_000, 1, kind)
+                .unwrap();
+            assert_eq!(c.total(), 3_000);
+            let r = equity_mc_multiway_sampled_checked(&[&hero, &[11, 24]], &[0, 1, 2, 3], &[], 3_000, 1, kind)
+                .unwrap();
+            assert_eq!(r[0].total(), 3_000);
+        }
+        // Uniform keeps the original random stream.
+        assert_eq!(
+            equity_mc_vs_random_sampled_checked(&hero, &[], &[], 1_000, 9, Sampling::Uniform),
+            equity_mc_vs_random_checked(&hero, &[], 1_000, 9)
+        );
+    }
+
+    #[test]
+    fn range_seat_sampling_is_unbiased() {
+        // Runouts drawn after the range deal stay unbiased whichever combo was dealt.
+        let hero = [12, 25];
+        let flop = [0, 14, 28];
+        let range = Range::from_hands(&[[11, 24], [10, 23]]).unwrap();
+        let exact = (equity_exact_vs_hand_checked(&hero, &[11, 24], &flop).unwrap().equity()
+            + equity_exact_vs_hand_checked(&hero, &[10, 23], &flop).unwrap().equity())
+            / 2.0;
+        let seats = [Seat::Range(&range)];
+        for kind in [Sampling::Stratified, Sampling::Systematic, Sampling::QuasiRandom] {
+            let mut mean = 0.0;
+            for seed in 1..=20 {
+                let r = equity_mc_ranges_multiway_sampled_checked(&hero, &seats, &flop, &[], 2070, seed, kind)
+                    .unwrap();
+                assert_eq!(r[0].total(), 2070);
+                mean += r[0].equity() / 20.0;
+            }
+            assert!((mean - exact).abs() < 0.005, "{:?}: {} vs {}", kind, mean, exact);
+
+            let c = equity_mc_crn_sampled_checked(&[hero, [51, 50]], &seats, &flop, &[], 2070, 3, kind).unwrap();
+            assert!((c.counts[0].equity() - exact).abs() < 0.03, "{:?}", kind);
+        }
+
+        // Preflop, stratified runouts also draw 3 cards beyond the turn/river stratum.
+        // Hero's deuces make any overdealt 2c show up as extra sets.
+        let deuces = [13, 26];
+        let exact = (equity_exact_vs_hand_checked(&deuces, &[11, 24], &[]).unwrap().equity()
+            + equity_exact_vs_hand_checked(&deuces, &[10, 23], &[]).unwrap().equity())
+            / 2.0;
+        for kind in [Sampling::Stratified, Sampling::Systematic, Sampling::QuasiRandom] {
+            let mut mean = 0.0;
+            for seed in 1..=20 {
+                let r = equity_mc_ranges_multiway_sampled_checked(&deuces, &seats, &[], &[], 2070, seed, kind)
+                    .unwrap();
+                mean += r[0].equity() / 20.0;
+            }
+            assert!((mean - exact).abs() < 0.005, "{:?} preflop: {} vs {}", kind, mean, exact);
+
+            let c = equity_mc_crn_sampled_checked(&[deuces, [51, 50]], &seats, &[], &[], 2070, 3, kind).unwrap();
+            assert!((c.counts[0].equity() - exact).abs() < 0.03, "{:?} preflop", kind);
+        }
+        assert_eq!(
+            equity_mc_ranges_multiway_sampled_checked(&hero, &seats, &flop, &[], 1_000, 9, Sampling::Uniform),
+            equity_mc_ranges_multiway_checked(&hero, &seats, &flop, 1_000, 9)
+        );
+    }
+
     #[test]
     fn multiway_errors() {
         let h1 = [0, 1];
@@ -0,0 +1,269 @@
+//! Target-precision Monte Carlo: sample in batches until the confidence interval
+//! is narrow enough or a budget runs out.
+//!
+//! Any Monte Carlo function can be driven through a closure taking
+//! `(iters, seed)`; batch `i` is seeded with `shard_seed(seed, i)`, so a run is
+//! reproducible for a given seed and rule (time budgets aside).
+
+use std::time::{Duration, Instant};
+
+use super::{shard_seed, EquityCounts, EquityError, MultiWayCounts};
+
+/// When to stop an adaptive Monte Carlo run.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StopRule {
+    /// Stop once the Wilson interval half-width is at most this (equity units).
+    pub epsilon: f64,
+    /// Normal quantile for the interval (1.96 = 95%).
+    pub z: f64,
+    /// Iterations per batch; precision is checked between batches.
+    pub batch: u64,
+    /// Hard cap on total iterations.
+    pub max_iters: u64,
+    /// Optional wall-clock budget, checked between batches.
+    pub max_time: Option<Duration>,
+}
+
+impl StopRule {
+    /// 95% interval of half-width `epsilon`, 10k batches, at most 100M iterations.
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            epsilon,
+            z: 1.96,
+            batch: 10_000,
+            max_iters: 100_000_000,
+            max_time: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StopReason {
+    /// The interval half-width reached `epsilon`.
+    Converged,
+    /// `max_iters` was reached first.
+    MaxIters,
+    /// `max_time` elapsed first.
+    MaxTime,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AdaptiveResult {
+    pub counts: EquityCounts,
+    /// Iterations actually run (equals `counts.total()`).
+    pub iters: u64,
+    pub stop: StopReason,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdaptiveMultiWayResult {
+    pub results: Vec<MultiWayCounts>,
+    /// Iterations actually run.
+    pub iters: u64,
+    pub stop: StopReason,
+}
+
+#[inline]
+fn half_width((lo, hi): (f64, f64)) -> f64 {
+    0.5 * (hi - lo)
+}
+
+/// Drive `run(iters, seed)` in batches until `rule` is met.
+fn run_batches<T>(
+    rule: &StopRule,
+    seed: u64,
+    mut acc: T,
+    mut run: impl FnMut(u64, u64) -> Result<T, EquityError>,
+    mut merge: impl FnMut(&mut T, T),
+    converged: impl Fn(&T) -> bool,
+) -> Result<(T, u64, StopReason), EquityError> {
+    let start = Instant::now();
+    let batch = rule.batch.max(1);
+    let mut done = 0u64;
+    let mut i = 0u64;
+    loop {
+        if done >= rule.max_iters {
+            return Ok((acc, done, StopReason::MaxIters));
+        }
+        let n = batch.min(rule.max_iters - done);
+        merge(&mut acc, run(n, shard_seed(seed, i))?);
+        done += n;
+        i += 1;
+
+        if converged(&acc) {
+            return Ok((acc, done, StopReason::Converged));
+        }
+        if let Some(limit) = rule.max_time {
+            if start.elapsed() >= limit {
+                return Ok((acc, done, StopReason::MaxTime));
+            }
+        }
+    }
+}
+
+/// Run a heads-up style Monte Carlo function until hero's equity is known to `rule.epsilon`.
+///
+/// ```
+/// use poker_eval::{equity_mc_adaptive, equity_mc_vs_random_checked, StopRule};
+///
+/// let hero = [12, 25]; // Ac Ad
+/// let res = equity_mc_adaptive(&StopRule::new(0.005), 42, |iters, seed| {
+///     equity_mc_vs_random_checked(&hero, &[], iters, seed)
+/// })
+/// .unwrap();
+/// assert!(res.iters > 0);
+/// ```
+pub fn equity_mc_adaptive<F>(rule: &StopRule, seed: u64, run: F) -> Result<AdaptiveResult, EquityError>
+where
+    F: FnMut(u64, u64) -> Result<EquityCounts, EquityError>,
+{
+    let (counts, iters, stop) = run_batches(
+        rule,
+        seed,
+        EquityCounts::default(),
+        run,
+        |acc, c| *acc += c,
+        |c| half_width(c.wilson_interval(rule.z)) <= rule.epsilon,
+    )?;
+    Ok(AdaptiveResult { counts, iters, stop })
+}
+
+/// Run a multi-way Monte Carlo function until every player's pot share is known to `rule.epsilon`.
+///
+/// Precision is measured on [`MultiWayCounts::wilson_interval`], so a k-way
+/// split counts as 1/k of the pot rather than half of it.
+pub fn equity_mc_adaptive_multiway<F>(
+    rule: &StopRule,
+    seed: u64,
+    run: F,
+) -> Result<AdaptiveMultiWayResult, EquityError>
+where
+    F: FnMut(u64, u64) -> Result<Vec<MultiWayCounts>, EquityError>,
+{
+    let (results, iters, stop) = run_batches(
+        rule,
+        seed,
+        Vec::new(),
+        run,
+        |acc, r| {
+            if acc.is_empty() {
+                *acc = r;
+            } else {
+                for (a, b) in acc.iter_mut().zip(r) {
+                    *a += b;
+                }
+            }
+        },
+        |r| r.iter().all(|c| half_width(c.wilson_interval(rule.z)) <= rule.epsilon),
+    )?;
+    Ok(AdaptiveMultiWayResult { results, iters, stop })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equity::{
+        equity_exact_vs_hand_checked, equity_mc_multiway_pot_share_checked, equity_mc_vs_hand_checked,
+    };
+
+    #[test]
+    fn adaptive_converges_within_epsilon() {
+        let hero = [12, 25];
+        let vill = [11, 24];
+        let flop = [0, 14, 28];
+        let exact = equity_exact_vs_hand_checked(&hero, &vill, &flop).unwrap();
+
+        let rule = StopRule::new(0.01);
+        let res = equity_mc_adaptive(&rule, 3, |n, s| {
+            equity_mc_vs_hand_checked(&hero, &vill, &flop, n, s)
+        })
+        .unwrap();
+
+        assert_eq!(res.stop, StopReason::Converged);
+        assert_eq!(res.iters, res.counts.total());
+        assert_eq!(res.iters % rule.batch, 0);
+        let (lo, hi) = res.counts.wilson_interval(rule.z);
+        assert!(hi - lo <= 0.02);
+        assert!((res.counts.equity() - exact.equity()).abs() < 0.02);
+    }
+
+    #[test]
+    fn adaptive_respects_max_iters() {
+        let rule = StopRule {
+            epsilon: 1e-6,
+            batch: 3_000,
+            max_iters: 10_000,
+            ..StopRule::new(0.0)
+        };
+        let res = equity_mc_adaptive(&rule, 1, |n, s| {
+            equity_mc_vs_hand_checked(&[0, 1], &[2, 3], &[], n, s)
+        })
+        .unwrap();
+        assert_eq!(res.stop, StopReason::MaxIters);
+        assert_eq!(res.iters, 10_000);
+        assert_eq!(res.counts.total(), 10_000);
+    }
+
+    #[test]
+    fn adaptive_respects_max_time() {
+        let rule = StopRule {
+            epsilon: 1e-9,
+            max_time: Some(Duration::ZERO),
+            ..StopRule::new(0.0)
+        };
+        let res = equity_mc_adaptive(&rule, 1, |n, s| {
+            equity_mc_vs_hand_checked(&[0, 1], &[2, 3], &[], n, s)
+        })
+        .unwrap();
+        assert_eq!(res.stop, StopReason::MaxTime);
+        assert_eq!(res.iters, rule.batch);
+    }
+
+    #[test]
+    fn adaptive_multiway_tracks_all_players() {
+        let hands = [[12, 25], [11, 24], [10, 23]];
+        let refs: Vec<&[u8; 2]> = hands.iter().collect();
+        let rule = StopRule::new(0.01);
+        let res = equity_mc_adaptive_multiway(&rule, 5, |n, s| {
+            equity_mc_multiway_pot_share_checked(&refs, &[], n, s)
+        })
+        .unwrap();
+        assert_eq!(res.stop, StopReason::Converged);
+        assert_eq!(res.results.len(), 3);
+        for r in &res.results {
+            assert_eq!(r.total(), res.iters);
+            let (lo, hi) = r.wilson_interval(rule.z);
+            assert!(hi - lo <= 0.02);
+        }
+    }
+
+    #[test]
+    fn adaptive_multiway_measures_pot_share() {
+        // Royal flush on board: every runout is a three-way chop, so the raw
+        // win + tie/2 equity sits at 1/2 while the pot share is 1/3.
+        let hands = [[0, 1], [13, 14], [26, 27]];
+        let refs: Vec<&[u8; 2]> = hands.iter().collect();
+        let board = [47, 48, 49, 50, 51];
+        let rule = StopRule::new(0.0095);
+        let res = equity_mc_adaptive_multiway(&rule, 2, |n, s| {
+            equity_mc_multiway_pot_share_checked(&refs, &board, n, s)
+        })
+        .unwrap();
+        assert_eq!(res.stop, StopReason::Converged);
+        assert_eq!(res.iters, rule.batch);
+        for r in &res.results {
+            assert_eq!(r.split_ways[3], res.iters);
+            assert!((r.equity() - 1.0 / 3.0).abs() < 1e-12);
+            assert!(half_width(r.wilson_interval(rule.z)) <= rule.epsilon);
+            assert!(half_width(r.counts.wilson_interval(rule.z)) > rule.epsilon);
+        }
+    }
+
+    #[test]
+    fn adaptive_propagates_errors() {
+        let res = equity_mc_adaptive(&StopRule::new(0.01), 1, |n, s| {
+            equity_mc_vs_hand_checked(&[0, 1], &[1, 2], &[], n, s)
+        });
+        assert_eq!(res, Err(EquityError::DuplicateCard(1)));
+    }
+}
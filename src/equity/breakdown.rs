@@ -0,0 +1,307 @@
+//! Equity split by final hand category: how hero wins and what hero loses to.
+//!
+//! The showdown loops already score every hand; these variants keep the category of
+//! each score alongside the outcome. Heads-up, results are also grouped by villain's
+//! category ("12% of losses are to a full house").
+
+use super::*;
+use crate::score::{Category, Score};
+
+/// Hero's results grouped by final category, indexed by `Category as usize`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CategoryBreakdown {
+    /// Grouped by hero's category.
+    pub by_hero: [EquityCounts; 9],
+    /// Grouped by villain's category; heads-up only (all zero in multi-way results).
+    pub by_villain: [EquityCounts; 9],
+}
+
+impl CategoryBreakdown {
+    /// All results, whatever the categories.
+    pub fn total(&self) -> EquityCounts {
+        let mut t = EquityCounts::default();
+        for c in &self.by_hero {
+            t += *c;
+        }
+        t
+    }
+
+    /// Share of hero's wins made with `cat`.
+    pub fn win_share(&self, cat: Category) -> f64 {
+        let all = self.total().win;
+        if all == 0 {
+            return 0.0;
+        }
+        self.by_hero[cat as usize].win as f64 / all as f64
+    }
+
+    /// Share of hero's losses that are to villain holding `cat` (heads-up).
+    pub fn loss_share(&self, cat: Category) -> f64 {
+        let all = self.total().lose;
+        if all == 0 {
+            return 0.0;
+        }
+        self.by_villain[cat as usize].lose as f64 / all as f64
+    }
+
+    #[inline(always)]
+    fn record(&mut self, hero: u32, villain: u32, w: u64) {
+        let out = if hero > villain {
+            Outcome::HeroWin
+        } else if hero < villain {
+            Outcome::VillainWin
+        } else {
+            Outcome::Tie
+        };
+        bump_counts_weighted(&mut self.by_hero[Score(hero).category() as usize], out, w);
+        bump_counts_weighted(&mut self.by_villain[Score(villain).category() as usize], out, w);
+    }
+}
+
+/// Add one showdown standing for `w` runouts to each player's breakdown, under the
+/// player's own category.
+fn record_multiway(out: &mut [CategoryBreakdown], results: &mut [MultiWayCounts], scores: &[u32], w: u64) {
+    for r in results.iter_mut() {
+        *r = MultiWayCounts::default();
+    }
+    record_showdown_weighted(results, scores, w);
+    for ((b, r), &s) in out.iter_mut().zip(results.iter()).zip(scores) {
+        b.by_hero[Score(s).category() as usize] += r.counts;
+    }
+}
+
+#[inline(always)]
+fn scores_on(board5: &[u8; 5], hero: &[u8; 2], villain: &[u8; 2]) -> (u32, u32) {
+    let mut scores = [0u32; 2];
+    score_players([hero, villain], board5, &mut scores);
+    (scores[0], scores[1])
+}
+
+/// [`equity_exact_vs_hand_checked_with_dead`] grouped by both players' final categories.
+pub fn equity_exact_vs_hand_breakdown_checked(
+    hero: &[u8; 2],
+    villain: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<CategoryBreakdown, EquityError> {
+    let used0 = validate_inputs(hero, Some(villain), board, dead)?;
+    check_deck(used0, 5 - board.len())?;
+
+    let mut out = CategoryBreakdown::default();
+    for_each_runout_class(used0, board, |board5, w| {
+        let (h, v) = scores_on(board5, hero, villain);
+        out.record(h, v, w);
+    });
+    Ok(out)
+}
+
+/// [`equity_mc_vs_hand_checked_with_dead`] grouped by both players' final categories.
+pub fn equity_mc_vs_hand_breakdown_checked(
+    hero: &[u8; 2],
+    villain: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<CategoryBreakdown, EquityError> {
+    let used0 = validate_inputs(hero, Some(villain), board, dead)?;
+    let missing = 5 - board.len();
+    check_deck(used0, missing)?;
+    let mut s = CardSampler52::new(seed);
+    let mut runouts = RunoutSampler::new(Sampling::Uniform, used0, missing, iters, &mut s);
+
+    let mut board5 = [0u8; 5];
+    board5[..board.len()].copy_from_slice(board);
+    let mut out = CategoryBreakdown::default();
+    for _ in 0..iters {
+        let mut used = used0;
+        runouts.next(&mut s, &mut used, &mut board5[board.len()..]);
+        let (h, v) = scores_on(&board5, hero, villain);
+        out.record(h, v, 1);
+    }
+    Ok(out)
+}
+
+/// [`equity_exact_multiway_checked_with_dead`] with each player's results grouped by that
+/// player's final category.
+pub fn equity_exact_multiway_breakdown_checked(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<Vec<CategoryBreakdown>, EquityError> {
+    let n = hands.len();
+    let used = validate_multiway(hands, board, dead)?;
+    check_deck(used, 5 - board.len())?;
+
+    let mut out = vec![CategoryBreakdown::default(); n];
+    let mut scores = vec![0u32; n];
+    let mut results = vec![MultiWayCounts::default(); n];
+    for_each_runout_class(used, board, |board5, w| {
+        score_players(hands.iter().copied(), board5, &mut scores);
+        record_multiway(&mut out, &mut results, &scores, w);
+    });
+    Ok(out)
+}
+
+/// [`equity_mc_vs_random_checked_with_dead`] grouped by both players' final categories.
+pub fn equity_mc_vs_random_breakdown_checked(
+    hero: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<CategoryBreakdown, EquityError> {
+    let used0 = validate_inputs(hero, None, board, dead)?;
+    let missing = 5 - board.len();
+    check_deck(used0, 2 + missing)?;
+    let mut s = CardSampler52::new(seed);
+    let mut runouts = RunoutSampler::new(Sampling::Uniform, used0, missing, iters, &mut s);
+
+    let mut board5 = [0u8; 5];
+    board5[..board.len()].copy_from_slice(board);
+    let mut villain = [0u8; 2];
+    let mut out = CategoryBreakdown::default();
+    for _ in 0..iters {
+        let mut used = used0;
+        sample_distinct_cards(&mut s, &mut used, &mut villain)?;
+        runouts.next(&mut s, &mut used, &mut board5[board.len()..]);
+        let (h, v) = scores_on(&board5, hero, &villain);
+        out.record(h, v, 1);
+    }
+    Ok(out)
+}
+
+/// [`equity_mc_multiway_checked_with_dead`] with each player's results grouped by that
+/// player's final category.
+pub fn equity_mc_multiway_breakdown_checked(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<Vec<CategoryBreakdown>, EquityError> {
+    let n = hands.len();
+    let used0 = validate_multiway(hands, board, dead)?;
+    let missing = 5 - board.len();
+    check_deck(used0, missing)?;
+    let mut s = CardSampler52::new(seed);
+    let mut runouts = RunoutSampler::new(Sampling::Uniform, used0, missing, iters, &mut s);
+
+    let mut board5 = [0u8; 5];
+    board5[..board.len()].copy_from_slice(board);
+    let mut out = vec![CategoryBreakdown::default(); n];
+    let mut scores = vec![0u32; n];
+    let mut results = vec![MultiWayCounts::default(); n];
+    for _ in 0..iters {
+        let mut used = used0;
+        runouts.next(&mut s, &mut used, &mut board5[board.len()..]);
+        score_players(hands.iter().copied(), &board5, &mut scores);
+        record_multiway(&mut out, &mut results, &scores, 1);
+    }
+    Ok(out)
+}
+
+/// [`equity_mc_ranges_multiway_checked_with_dead`] with each player's results grouped by that
+/// player's final category: hero first, then villains in seat order.
+pub fn equity_mc_ranges_multiway_breakdown_checked(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<Vec<CategoryBreakdown>, EquityError> {
+    let n = villains.len() + 1;
+    let (mut hands, used0) = validate_seats(hero, villains, board, dead)?;
+    let dealer = SeatDealer::new(villains, used0)?;
+    let missing = 5 - board.len();
+    check_deck(used0, dealer.cards() + missing)?;
+    let mut s = CardSampler52::new(seed);
+    let live = 52 - used0.count_ones() as usize - dealer.cards();
+    let mut runouts = RunoutSampler::positional(Sampling::Uniform, live, missing, iters, &mut s);
+
+    let mut board5 = [0u8; 5];
+    board5[..board.len()].copy_from_slice(board);
+    let mut out = vec![CategoryBreakdown::default(); n];
+    let mut scores = vec![0u32; n];
+    let mut results = vec![MultiWayCounts::default(); n];
+    for _ in 0..iters {
+        let mut used = dealer.deal(&mut s, used0, &mut hands)?;
+        runouts.next(&mut s, &mut used, &mut board5[board.len()..]);
+        score_players(&hands, &board5, &mut scores);
+        record_multiway(&mut out, &mut results, &scores, 1);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breakdown_sums_to_plain_equity() {
+        // Ac Qc vs 8d 8h on 8c 5c 2s.
+        let hero = [12, 10];
+        let villain = [19, 32];
+        let board = [6, 3, 39];
+        let b = equity_exact_vs_hand_breakdown_checked(&hero, &villain, &board, &[]).unwrap();
+        let plain = equity_exact_vs_hand_checked(&hero, &villain, &board).unwrap();
+        assert_eq!(b.total(), plain);
+        let mut by_villain = EquityCounts::default();
+        for c in &b.by_villain {
+            by_villain += *c;
+        }
+        assert_eq!(by_villain, plain);
+
+        // Hero only wins with a flush or better; villain never loses holding quads.
+        assert!(b.win_share(Category::Flush) > 0.9);
+        assert_eq!(b.by_hero[Category::OnePair as usize].win, 0);
+        assert_eq!(b.by_villain[Category::Quads as usize].win, 0);
+        assert!(b.loss_share(Category::Trips) > b.loss_share(Category::FullHouse));
+
+        let mc = equity_mc_vs_hand_breakdown_checked(&hero, &villain, &board, &[], 20_000, 7).unwrap();
+        assert_eq!(mc.total().total(), 20_000);
+        assert!((mc.win_share(Category::Flush) - b.win_share(Category::Flush)).abs() < 0.05);
+    }
+
+    #[test]
+    fn multiway_breakdown_sums_per_player() {
+        let hands: [&[u8; 2]; 3] = [&[12, 25], &[11, 24], &[7, 6]];
+        let board = [0, 14, 41];
+        let b = equity_exact_multiway_breakdown_checked(&hands, &board, &[]).unwrap();
+        let plain = equity_exact_multiway_checked(&hands, &board).unwrap();
+        for (bi, p) in b.iter().zip(&plain) {
+            assert_eq!(bi.total(), *p);
+            assert_eq!(bi.by_villain, [EquityCounts::default(); 9]);
+        }
+
+        // Monte Carlo breakdowns follow the plain runs' random streams.
+        let mc = equity_mc_multiway_breakdown_checked(&hands, &board, &[], 5_000, 3).unwrap();
+        let plain = equity_mc_multiway_checked_with_dead(&hands, &board, &[], 5_000, 3).unwrap();
+        for (bi, p) in mc.iter().zip(&plain) {
+            assert_eq!(bi.total(), *p);
+        }
+
+        let range = Range::from_hands(&[[11, 24], [10, 23]]).unwrap();
+        let seats = [Seat::Range(&range), Seat::Random];
+        let mc = equity_mc_ranges_multiway_breakdown_checked(&[12, 25], &seats, &board, &[], 5_000, 3).unwrap();
+        let plain = equity_mc_ranges_multiway_checked(&[12, 25], &seats, &board, 5_000, 3).unwrap();
+        assert_eq!(mc.len(), 3);
+        for (bi, p) in mc.iter().zip(&plain) {
+            assert_eq!(bi.total(), *p);
+        }
+        // The range seat only holds kings or queens: a pair at least.
+        assert_eq!(mc[1].by_hero[Category::HighCard as usize], EquityCounts::default());
+    }
+
+    #[test]
+    fn vs_random_breakdown_matches_plain_run() {
+        let hero = [12, 25];
+        let board = [0, 14, 41];
+        let b = equity_mc_vs_random_breakdown_checked(&hero, &board, &[], 10_000, 5).unwrap();
+        let plain = equity_mc_vs_random_checked_with_dead(&hero, &board, &[], 10_000, 5).unwrap();
+        assert_eq!(b.total(), plain);
+        assert!(b.loss_share(Category::Trips) > 0.0);
+        assert_eq!(b.by_hero[Category::HighCard as usize], EquityCounts::default());
+    }
+}
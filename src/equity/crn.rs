@@ -0,0 +1,288 @@
+//! Common random numbers: several hero hands evaluated on the same sampled deals.
+//!
+//! Villain hands and runouts are drawn once per sample from the deck without any hero
+//! cards; each hero hand then plays every sample that does not share a card with it.
+//! Because candidates see the same deals, the noise in their equity differences largely
+//! cancels, and differences are resolved far faster than with independent runs.
+
+use super::*;
+
+/// Per-hand results of [`equity_mc_crn_checked`] plus the pairwise statistics needed for
+/// the standard error of each difference.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairedEquity {
+    /// Counts for hero hand `i` over the samples it was dealt into.
+    pub counts: Vec<MultiWayCounts>,
+    /// Sums over samples valid for both hands, at `i * len + j` for i < j.
+    pairs: Vec<PairStats>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct PairStats {
+    n: u64,
+    sum_i: f64,
+    sum_j: f64,
+    sum_ij: f64,
+}
+
+impl PairedEquity {
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    #[inline(always)]
+    fn pair_index(&self, i: usize, j: usize) -> usize {
+        let h = self.counts.len();
+        i * h + j
+    }
+
+    /// Number of samples both hands were dealt into.
+    pub fn shared(&self, i: usize, j: usize) -> u64 {
+        let (a, b) = (i.min(j), i.max(j));
+        if a == b {
+            return self.counts[a].total();
+        }
+        self.pairs[self.pair_index(a, b)].n
+    }
+
+    /// Equity of hand `i` minus equity of hand `j`.
+    pub fn diff(&self, i: usize, j: usize) -> f64 {
+        self.counts[i].equity() - self.counts[j].equity()
+    }
+
+    /// Standard error of [`diff`](Self::diff), using the covariance measured on the
+    /// shared samples: `Var(m_i) + Var(m_j) - 2 * n_ij * cov_ij / (n_i * n_j)`.
+    pub fn diff_std_error(&self, i: usize, j: usize) -> f64 {
+        if i == j {
+            return 0.0;
+        }
+        let (a, b) = (i.min(j), i.max(j));
+        let (ca, cb) = (&self.counts[a], &self.counts[b]);
+        let (na, nb) = (ca.total() as f64, cb.total() as f64);
+        if na < 2.0 || nb < 2.0 {
+            return f64::INFINITY;
+        }
+        let va = ca.std_error().powi(2);
+        let vb = cb.std_error().powi(2);
+
+        let p = &self.pairs[self.pair_index(a, b)];
+        let cov_term = if p.n >= 2 {
+            let n = p.n as f64;
+            let cov = (p.sum_ij - p.sum_i * p.sum_j / n) / (n - 1.0);
+            n * cov / (na * nb)
+        } else {
+            0.0
+        };
+        (va + vb - 2.0 * cov_term).max(0.0).sqrt()
+    }
+}
+
+/// Monte Carlo equity of several hero hands against the *same* sampled villain hands
+/// and runouts (common random numbers).
+/// - `heroes`: candidate hands; they may share cards with each other
+/// - `villains`: 1..8 seats, each a known hand, weighted range or random hand
+/// - `board` length: 0..5
+/// - a sample is skipped for every hero hand it shares a card with, so each hand's
+///   counts follow exactly its own deal distribution
+pub fn equity_mc_crn_checked(
+    heroes: &[[u8; 2]],
+    villains: &[Seat],
+    board: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<PairedEquity, EquityError> {
+    equity_mc_crn_checked_with_dead(heroes, villains, board, &[], iters, seed)
+}
+
+/// [`equity_mc_crn_checked`] with `dead` cards (folded or exposed) removed from the deck.
+pub fn equity_mc_crn_checked_with_dead(
+    heroes: &[[u8; 2]],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<PairedEquity, EquityError> {
+    equity_mc_crn_sampled_checked(heroes, villains, board, dead, iters, seed, Sampling::Uniform)
+}
+
+/// [`equity_mc_crn_checked_with_dead`] with a choice of runout [`Sampling`]; runouts are drawn
+/// after the villains are dealt, as positions among the cards left.
+pub fn equity_mc_crn_sampled_checked(
+    heroes: &[[u8; 2]],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    sampling: Sampling,
+) -> Result<PairedEquity, EquityError> {
+    if heroes.is_empty() || villains.is_empty() {
+        return Err(EquityError::TooFewPlayers);
+    }
+    if villains.len() >= MAX_PLAYERS {
+        return Err(EquityError::TooManyPlayers);
+    }
+    if board.len() > 5 {
+        return Err(EquityError::TooManyBoardCards(board.len()));
+    }
+
+    let mut hands = vec![[0u8; 2]; villains.len() + 1];
+    let mut used0: u64 = 0;
+    for (i, seat) in villains.iter().enumerate() {
+        if let Seat::Hand(h) = seat {
+            add_used(&mut used0, h[0])?;
+            add_used(&mut used0, h[1])?;
+            hands[i + 1] = *h;
+        }
+    }
+    for &c in board {
+        add_used(&mut used0, c)?;
+    }
+    for &c in dead {
+        add_used(&mut used0, c)?;
+    }
+    let mut hero_masks = Vec::with_capacity(heroes.len());
+    for h in heroes {
+        let mut m = used0;
+        add_used(&mut m, h[0])?;
+        add_used(&mut m, h[1])?;
+        hero_masks.push(m & !used0);
+    }
+
+    let dealer = SeatDealer::new(villains, used0)?;
+    let h = heroes.len();
+    let missing = 5usize.saturating_sub(board.len());
+    check_deck(used0, dealer.cards() + missing)?;
+    let mut counts = vec![MultiWayCounts::default(); h];
+    let mut pairs = vec![PairStats::default(); h * h];
+    let mut s = CardSampler52::new(seed);
+    let live = 52 - used0.count_ones() as usize - dealer.cards();
+    let mut runouts = RunoutSampler::positional(sampling, live, missing, iters, &mut s);
+
+    let mut board5 = [0u8; 5];
+    for (i, &c) in board.iter().enumerate() {
+        board5[i] = c;
+    }
+
+    let mut fill = [0u8; 5];
+    let mut scores = vec![0u32; villains.len()];
+    // Pot share of each hero hand in the current sample (None = skipped).
+    let mut shares: Vec<Option<f64>> = vec![None; h];
+
+    for _ in 0..iters {
+        let mut used = dealer.deal(&mut s, used0, &mut hands)?;
+        runouts.next(&mut s, &mut used, &mut fill[..missing]);
+        for i in 0..missing {
+            board5[board.len() + i] = fill[i];
+        }
+        let dealt = used & !used0;
+
+        let mut bb_board = BitBoard4x13::new();
+        for &c in &board5 {
+            bb_board.add_id(c);
+        }
+        score_players(&hands[1..], &board5, &mut scores);
+        let best = scores.iter().copied().max().unwrap_or(0);
+        let nbest = scores.iter().filter(|&&x| x == best).count();
+
+        for (k, hero) in heroes.iter().enumerate() {
+            if (hero_masks[k] & dealt) != 0 {
+                shares[k] = None;
+                continue;
+            }
+            let mut b = bb_board;
+            b.add_id(hero[0]);
+            b.add_id(hero[1]);
+            let hs = evaluate_u32(&b).0;
+            let c = &mut counts[k];
+            shares[k] = Some(if hs > best {
+                c.counts.win += 1;
+                1.0
+            } else if hs == best {
+                c.counts.tie += 1;
+                c.split_ways[nbest + 1] += 1;
+                1.0 / (nbest + 1) as f64
+            } else {
+                c.counts.lose += 1;
+                0.0
+            });
+        }
+
+        for i in 0..h {
+            let Some(xi) = shares[i] else { continue };
+            for j in (i + 1)..h {
+                let Some(xj) = shares[j] else { continue };
+                let p = &mut pairs[i * h + j];
+                p.n += 1;
+                p.sum_i += xi;
+                p.sum_j += xj;
+                p.sum_ij += xi * xj;
+            }
+        }
+    }
+
+    Ok(PairedEquity { counts, pairs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crn_matches_exact_per_hand() {
+        let heroes = [[12, 25], [10, 23], [51, 50]]; // AcAd, QcQd, AsKs
+        let vill = [11, 24]; // KcKd
+        let flop = [0, 14, 28];
+        let res = equity_mc_crn_checked(&heroes, &[Seat::Hand(vill)], &flop, 40_000, 11).unwrap();
+        for (k, hero) in heroes.iter().enumerate() {
+            let exact = equity_exact_vs_hand_checked(hero, &vill, &flop).unwrap();
+            assert!(
+                (res.counts[k].equity() - exact.equity()).abs() < 0.01,
+                "hand {}: {} vs {}",
+                k,
+                res.counts[k].equity(),
+                exact.equity()
+            );
+        }
+    }
+
+    #[test]
+    fn crn_shrinks_difference_error() {
+        // Two similar hands vs a random villain: paired error is far below independent error.
+        let heroes = [[12, 11], [12, 10]]; // AcKc, AcQc
+        let res = equity_mc_crn_checked(&heroes, &[Seat::Random], &[], 20_000, 3).unwrap();
+        let paired = res.diff_std_error(0, 1);
+        let independent = (res.counts[0].std_error().powi(2) + res.counts[1].std_error().powi(2)).sqrt();
+        assert!(paired < 0.7 * independent, "paired {} vs independent {}", paired, independent);
+        assert!(res.diff(0, 1) > 0.0);
+        assert_eq!(res.diff(0, 1), -res.diff(1, 0));
+        assert_eq!(res.diff_std_error(0, 1), res.diff_std_error(1, 0));
+    }
+
+    #[test]
+    fn crn_skips_conflicting_samples() {
+        // 7 of 52 cards are dealt per sample, so a hand survives with p = C(50,7)/C(52,7) ~ 0.747.
+        let heroes = [[12, 11], [12, 24]]; // AcKc, AcKd share the Ac
+        let res = equity_mc_crn_checked(&heroes, &[Seat::Random], &[], 5_000, 8).unwrap();
+        assert!(res.counts.iter().all(|c| c.total() > 3_500 && c.total() < 3_970));
+        assert!(res.shared(0, 1) < res.counts[0].total());
+        assert_eq!(res.shared(0, 0), res.counts[0].total());
+    }
+
+    #[test]
+    fn crn_errors() {
+        let res = equity_mc_crn_checked(&[[0, 1]], &[Seat::Random], &[1, 2, 3], 10, 0);
+        assert_eq!(res, Err(EquityError::DuplicateCard(1)));
+        let res = equity_mc_crn_checked(&[], &[Seat::Random], &[], 10, 0);
+        assert_eq!(res, Err(EquityError::TooFewPlayers));
+        let res = equity_mc_crn_checked_with_dead(&[[0, 1]], &[Seat::Hand([2, 3])], &[], &[3], 10, 0);
+        assert_eq!(res, Err(EquityError::DuplicateCard(3)));
+    }
+}
@@ -0,0 +1,261 @@
+//! Suit-isomorphic runout classes for exact enumeration.
+//!
+//! Only a suit holding at least three board cards can give anyone a flush, and a
+//! five-card board has at most one such suit. Every player's hand value is therefore
+//! fixed by the board's rank multiset plus, if a flush suit exists, which ranks of
+//! that suit are on board. Runouts agreeing on those are isomorphic for all players
+//! at once, so exact enumeration evaluates one representative per class and weights
+//! it by the number of runouts in the class. Heads-up preflop that is about 110k
+//! showdowns instead of 1.7M.
+
+use super::sampling::BINOM;
+
+/// `CHOOSE4[n][k]` = n choose k for n <= 4.
+const CHOOSE4: [[u64; 5]; 5] = [
+    [1, 0, 0, 0, 0],
+    [1, 1, 0, 0, 0],
+    [1, 2, 1, 0, 0],
+    [1, 3, 3, 1, 0],
+    [1, 4, 6, 4, 1],
+];
+
+/// Call `f(board5, weight)` once per class of runouts completing `board` from the
+/// cards outside `used` (`used` must include the board). Weights sum to the number
+/// of runouts.
+pub(super) fn for_each_runout_class(used: u64, board: &[u8], mut f: impl FnMut(&[u8; 5], u64)) {
+    let mut board5 = [0u8; 5];
+    board5[..board.len()].copy_from_slice(board);
+    let base = board.len();
+    let missing = 5 - base;
+    if missing == 0 {
+        f(&board5, 1);
+        return;
+    }
+
+    // Available ranks per suit, and available suits per rank.
+    let mut avail = [0u16; 4];
+    for (s, a) in avail.iter_mut().enumerate() {
+        *a = !((used >> (13 * s)) as u16) & 0x1FFF;
+    }
+    let mut suits_of = [0u8; 13];
+    for (r, m) in suits_of.iter_mut().enumerate() {
+        for (s, a) in avail.iter().enumerate() {
+            if (a >> r) & 1 != 0 {
+                *m |= 1 << s;
+            }
+        }
+    }
+    let mut on_board = [0usize; 4];
+    for &c in board {
+        on_board[(c / 13) as usize] += 1;
+    }
+
+    // Suit `fs` reaches three: its ranks matter, the other cards only by rank.
+    // Each class's weight is also taken off the rank-only total of its rank multiset.
+    let mut flushing = vec![0u64; BINOM[12 + missing][missing] as usize];
+    let mut ranks = [0u8; 5];
+    for fs in 0..4 {
+        let need = 3usize.saturating_sub(on_board[fs]);
+        if need > missing {
+            continue;
+        }
+        let others = suits_of.map(|m| m & !(1 << fs));
+        let maxk = others.map(|m| m.count_ones() as u8);
+        let af = avail[fs];
+        // Every subset of the suit's available ranks (at most 2^13).
+        let mut sub = af;
+        loop {
+            let j = sub.count_ones() as usize;
+            if j >= need && j <= missing {
+                let mut flush_ranks = [0u8; 5];
+                let mut n = 0;
+                for r in 0..13u8 {
+                    if (sub >> r) & 1 != 0 {
+                        flush_ranks[n] = r;
+                        board5[base + n] = fs as u8 * 13 + r;
+                        n += 1;
+                    }
+                }
+                for_each_rank_list(missing - j, &maxk, &mut ranks, &mut |rest| {
+                    let w = list_weight(rest, &maxk);
+                    let mut m = 0u8;
+                    for (i, &r) in rest.iter().enumerate() {
+                        if i == 0 || rest[i - 1] != r {
+                            m = others[r as usize];
+                        }
+                        board5[base + j + i] = m.trailing_zeros() as u8 * 13 + r;
+                        m &= m - 1;
+                    }
+                    flushing[merged_index(&flush_ranks[..j], rest)] += w;
+                    f(&board5, w);
+                });
+            }
+            if sub == 0 {
+                break;
+            }
+            sub = (sub - 1) & af;
+        }
+    }
+
+    // No suit reaches three: only the board ranks matter.
+    if on_board.iter().all(|&n| n <= 2) {
+        let cap = on_board.map(|n| 2 - n as u8);
+        let maxk = suits_of.map(|m| m.count_ones() as u8);
+        for_each_rank_list(missing, &maxk, &mut ranks, &mut |list| {
+            let w = list_weight(list, &maxk) - flushing[merged_index(&[], list)];
+            if w > 0 {
+                let mut load = [0u8; 4];
+                let found = find_capped(list, &suits_of, &cap, &mut load, &mut board5[base..]);
+                debug_assert!(found);
+                f(&board5, w);
+            }
+        });
+    }
+}
+
+/// Every non-decreasing list of `size` ranks using rank `r` at most `maxk[r]` times.
+fn for_each_rank_list(size: usize, maxk: &[u8; 13], buf: &mut [u8; 5], f: &mut impl FnMut(&[u8])) {
+    fn rec(len: usize, size: usize, run: u8, maxk: &[u8; 13], buf: &mut [u8; 5], f: &mut impl FnMut(&[u8])) {
+        if len == size {
+            f(&buf[..size]);
+            return;
+        }
+        let start = if len == 0 { 0 } else { buf[len - 1] };
+        for r in start..13 {
+            let run = if len > 0 && buf[len - 1] == r { run + 1 } else { 1 };
+            if run > maxk[r as usize] {
+                continue;
+            }
+            buf[len] = r;
+            rec(len + 1, size, run, maxk, buf, f);
+        }
+    }
+    rec(0, size, 0, maxk, buf, f);
+}
+
+/// Number of ways to deal the sorted ranks in `list` when rank `r` has `maxk[r]` suits.
+#[inline]
+fn list_weight(list: &[u8], maxk: &[u8; 13]) -> u64 {
+    let mut w = 1u64;
+    let mut i = 0;
+    while i < list.len() {
+        let r = list[i];
+        let run = list[i..].iter().take_while(|&&x| x == r).count();
+        w *= CHOOSE4[maxk[r as usize] as usize][run];
+        i += run;
+    }
+    w
+}
+
+/// Colex index of the rank multiset `a + b` (both sorted) among all C(12 + m, m)
+/// multisets of its size m: sorted ranks r_i map to distinct values r_i + i.
+#[inline]
+fn merged_index(a: &[u8], b: &[u8]) -> usize {
+    let (mut x, mut y) = (0, 0);
+    let mut idx = 0u64;
+    for i in 0..a.len() + b.len() {
+        let r = if y == b.len() || (x < a.len() && a[x] <= b[y]) {
+            x += 1;
+            a[x - 1]
+        } else {
+            y += 1;
+            b[y - 1]
+        };
+        idx += BINOM[r as usize + i][i + 1];
+    }
+    idx as usize
+}
+
+/// Give the sorted ranks in `list` distinct suits per rank from `suits_of`, with at
+/// most `cap[s]` cards of suit `s`, writing the cards to `out`. Returns false if no
+/// such assignment exists.
+fn find_capped(list: &[u8], suits_of: &[u8; 13], cap: &[u8; 4], load: &mut [u8; 4], out: &mut [u8]) -> bool {
+    let Some(&r) = list.first() else {
+        return true;
+    };
+    let mut m = suits_of[r as usize];
+    while m != 0 {
+        let s = m.trailing_zeros() as usize;
+        m &= m - 1;
+        if load[s] == cap[s] {
+            continue;
+        }
+        out[0] = s as u8 * 13 + r;
+        // Equal ranks take increasing suits so no card repeats.
+        let mut next = *suits_of;
+        next[r as usize] &= !((2u8 << s) - 1);
+        load[s] += 1;
+        let ok = find_capped(&list[1..], &next, cap, load, &mut out[1..]);
+        load[s] -= 1;
+        if ok {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use super::*;
+
+    fn used_of(cards: &[u8]) -> u64 {
+        cards.iter().fold(0, |u, &c| u | (1u64 << c))
+    }
+
+    #[test]
+    fn weights_sum_to_runout_count() {
+        for (known, board) in [
+            (vec![12u8, 25, 11, 24], vec![]),
+            (vec![51, 50, 0, 1, 2], vec![3u8]),
+            (vec![0, 13], vec![26u8, 39]),
+        ] {
+            let mut all = known.clone();
+            all.extend(&board);
+            let used = used_of(&all);
+            let rem = 52 - used.count_ones() as u64;
+            let missing = 5 - board.len();
+            let mut total = 0u64;
+            let mut classes = 0u64;
+            for_each_runout_class(used, &board, |b5, w| {
+                assert_eq!(&b5[..board.len()], &board[..]);
+                assert_eq!(used_of(b5) & used, used_of(&board));
+                assert_eq!(used_of(b5).count_ones(), 5);
+                total += w;
+                classes += 1;
+            });
+            let expect = (0..missing as u64).fold(1, |acc, i| acc * (rem - i) / (i + 1));
+            assert_eq!(total, expect);
+            assert!(classes < expect || missing < 2);
+        }
+    }
+
+    #[test]
+    fn classes_match_full_enumeration() {
+        // Three players, one to four missing cards, monotone and two-tone boards.
+        let hands = [[12u8, 25], [50, 49], [5, 31]];
+        let boards: [&[u8]; 6] = [&[48], &[0, 40], &[39, 40, 41], &[1, 14, 40], &[2, 15, 3, 44], &[0, 1, 2, 3, 4]];
+        for board in boards {
+            let dead = [20u8];
+            let mut used = used_of(board) | used_of(&dead);
+            for h in &hands {
+                used |= used_of(h);
+            }
+            let mut buf = [0u8; 52];
+            let nrem = fill_remaining_cards(used, &mut buf);
+            let mut scores = [0u32; 3];
+
+            let mut brute = vec![MultiWayCounts::default(); 3];
+            enumerate_board_completions(&buf[..nrem], board, 5 - board.len(), |b5| {
+                score_players(&hands, &b5, &mut scores);
+                record_showdown(&mut brute, &scores);
+            });
+            let mut iso = vec![MultiWayCounts::default(); 3];
+            for_each_runout_class(used, board, |b5, w| {
+                score_players(&hands, b5, &mut scores);
+                record_showdown_weighted(&mut iso, &scores, w);
+            });
+            assert_eq!(brute, iso, "board {:?}", board);
+        }
+    }
+}
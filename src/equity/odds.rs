@@ -0,0 +1,272 @@
+//! Probability of finishing as each hand category, with no opponent involved.
+//!
+//! Hole cards plus a partial board are completed to five board cards and hero's
+//! final category is counted per runout ("flush by the river from a flop flush
+//! draw"). Exact results walk the suit-isomorphic runout classes; the Monte Carlo
+//! variant samples runouts, and [`category_odds_checked`] picks between the two by
+//! the number of runouts.
+
+use super::*;
+use crate::lut13::straight_end13;
+use crate::score::{Category, Score};
+
+/// Runouts per final category, indexed by `Category as usize`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct CategoryOdds {
+    pub counts: [u64; 9],
+}
+
+impl CategoryOdds {
+    /// Runouts counted.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Probability of finishing with exactly `cat`.
+    pub fn probability(&self, cat: Category) -> f64 {
+        let t = self.total();
+        if t == 0 {
+            return 0.0;
+        }
+        self.counts[cat as usize] as f64 / t as f64
+    }
+
+    /// Probability of finishing with `cat` or better.
+    pub fn at_least(&self, cat: Category) -> f64 {
+        let t = self.total();
+        if t == 0 {
+            return 0.0;
+        }
+        self.counts[cat as usize..].iter().sum::<u64>() as f64 / t as f64
+    }
+}
+
+#[inline(always)]
+fn category_on(hole: &[u8; 2], board5: &[u8; 5]) -> Category {
+    let mut score = [0u32; 1];
+    score_players([hole], board5, &mut score);
+    Score(score[0]).category()
+}
+
+/// Exact odds of each final category for `hole` on a 0-5 card `board`, over every
+/// runout that avoids `dead`.
+pub fn category_odds_exact_checked(hole: &[u8; 2], board: &[u8], dead: &[u8]) -> Result<CategoryOdds, EquityError> {
+    let used = validate_inputs(hole, None, board, dead)?;
+    check_deck(used, 5 - board.len())?;
+    let mut out = CategoryOdds::default();
+    for_each_runout_class(used, board, |board5, w| {
+        out.counts[category_on(hole, board5) as usize] += w;
+    });
+    Ok(out)
+}
+
+/// Monte Carlo estimate of [`category_odds_exact_checked`] from `iters` runouts.
+pub fn category_odds_mc_checked(
+    hole: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<CategoryOdds, EquityError> {
+    let used0 = validate_inputs(hole, None, board, dead)?;
+    let missing = 5 - board.len();
+    check_deck(used0, missing)?;
+    let mut s = CardSampler52::new(seed);
+    let mut runouts = RunoutSampler::new(Sampling::Uniform, used0, missing, iters, &mut s);
+
+    let mut board5 = [0u8; 5];
+    board5[..board.len()].copy_from_slice(board);
+    let mut out = CategoryOdds::default();
+    for _ in 0..iters {
+        let mut used = used0;
+        runouts.next(&mut s, &mut used, &mut board5[board.len()..]);
+        out.counts[category_on(hole, &board5) as usize] += 1;
+    }
+    Ok(out)
+}
+
+/// [`category_odds_exact_checked`] when there are at most `iters` runouts, otherwise
+/// [`category_odds_mc_checked`] over `iters` runouts.
+pub fn category_odds_checked(
+    hole: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+) -> Result<CategoryOdds, EquityError> {
+    let used = validate_inputs(hole, None, board, dead)?;
+    let missing = 5 - board.len();
+    check_deck(used, missing)?;
+    let live = 52 - used.count_ones() as usize;
+    if sampling::BINOM[live][missing] <= iters {
+        category_odds_exact_checked(hole, board, dead)
+    } else {
+        category_odds_mc_checked(hole, board, dead, iters, seed)
+    }
+}
+
+/// Category of seven cards with these rank counts when no five share a suit.
+fn rank_category(counts: &[u8; 13]) -> Category {
+    let mut mask = 0u16;
+    let (mut pairs, mut trips, mut quads) = (0, 0, 0);
+    for (r, &c) in counts.iter().enumerate() {
+        if c > 0 {
+            mask |= 1 << r;
+        }
+        pairs += usize::from(c >= 2);
+        trips += usize::from(c >= 3);
+        quads += usize::from(c == 4);
+    }
+    if quads > 0 {
+        Category::Quads
+    } else if trips > 0 && pairs >= 2 {
+        Category::FullHouse
+    } else if straight_end13(mask) >= 0 {
+        Category::Straight
+    } else if trips > 0 {
+        Category::Trips
+    } else if pairs >= 2 {
+        Category::TwoPair
+    } else if pairs == 1 {
+        Category::OnePair
+    } else {
+        Category::HighCard
+    }
+}
+
+/// Call `f` with every way to put `left` more cards on ranks `rank..13`, at most
+/// `max` per rank.
+fn for_each_rank_count(counts: &mut [u8; 13], rank: usize, left: u8, max: u8, f: &mut impl FnMut(&[u8; 13])) {
+    if left == 0 {
+        f(counts);
+        return;
+    }
+    if rank == 13 {
+        return;
+    }
+    let base = counts[rank];
+    for c in 0..=left.min(max) {
+        counts[rank] = base + c;
+        for_each_rank_count(counts, rank + 1, left - c, max, f);
+    }
+    counts[rank] = base;
+}
+
+/// Unconditional 7-card category frequencies: every 7-card hand from a full deck
+/// counted once (133,784,560 in total): 23,294,460 high card, 58,627,800 one pair,
+/// 31,433,400 two pair, 6,461,620 trips, 6,180,020 straights, 4,047,644 flushes,
+/// 3,473,184 full houses, 224,848 quads and 41,584 straight flushes.
+pub fn category_odds_seven_cards() -> CategoryOdds {
+    let binom = &sampling::BINOM;
+    let mut out = CategoryOdds::default();
+    // Every hand by its ranks alone, as if it had no flush.
+    let mut counts = [0u8; 13];
+    for_each_rank_count(&mut counts, 0, 7, 4, &mut |counts| {
+        let ways: u64 = counts.iter().map(|&c| binom[4][c as usize]).product();
+        out.counts[rank_category(counts) as usize] += ways;
+    });
+    // Seven cards hold at most one suit of five or more, and then no full house or
+    // quads: move those hands to a flush or straight flush by the suited ranks.
+    for suited in 0u16..1 << 13 {
+        let k = suited.count_ones() as u8;
+        if !(5..=7).contains(&k) {
+            continue;
+        }
+        let cat = if straight_end13(suited) >= 0 {
+            Category::StraightFlush
+        } else {
+            Category::Flush
+        };
+        let mut counts = [0u8; 13];
+        for (r, c) in counts.iter_mut().enumerate() {
+            *c = (suited >> r & 1) as u8;
+        }
+        let mut others = [0u8; 13];
+        for_each_rank_count(&mut others, 0, 7 - k, 3, &mut |others| {
+            let ways: u64 = 4 * others.iter().map(|&c| binom[3][c as usize]).product::<u64>();
+            let mut all = counts;
+            for (a, &o) in all.iter_mut().zip(others) {
+                *a += o;
+            }
+            out.counts[rank_category(&all) as usize] -= ways;
+            out.counts[cat as usize] += ways;
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flop_flush_draw_odds() {
+        // Ah Kh on Qh 7h 2c: nine hearts among 47 cards.
+        let hole = [38, 37];
+        let board = [36, 31, 0];
+        let odds = category_odds_exact_checked(&hole, &board, &[]).unwrap();
+        assert_eq!(odds.total(), 47 * 46 / 2);
+        let no_heart = 38 * 37 / 2;
+        // Jh Th is the royal flush; every other heart runout is a plain flush.
+        assert_eq!(odds.counts[Category::Flush as usize] + odds.counts[Category::StraightFlush as usize], 1081 - no_heart);
+        assert_eq!(odds.counts[Category::StraightFlush as usize], 1);
+
+        // Matches the full walk over runouts.
+        let mut rem = [0u8; 52];
+        let used = validate_inputs(&hole, None, &board, &[]).unwrap();
+        let n = fill_remaining_cards(used, &mut rem);
+        let mut expect = CategoryOdds::default();
+        enumerate_board_completions(&rem[..n], &board, 2, |b5| {
+            expect.counts[category_on(&hole, &b5) as usize] += 1;
+        });
+        assert_eq!(odds, expect);
+
+        // A dead heart removes one out.
+        let dead = category_odds_exact_checked(&hole, &board, &[30]).unwrap();
+        assert!(dead.at_least(Category::Flush) < odds.at_least(Category::Flush));
+
+        let mc = category_odds_mc_checked(&hole, &board, &[], 20_000, 3).unwrap();
+        assert_eq!(mc.total(), 20_000);
+        assert!((mc.at_least(Category::Flush) - odds.at_least(Category::Flush)).abs() < 0.02);
+
+        assert_eq!(
+            category_odds_exact_checked(&hole, &[38], &[]),
+            Err(EquityError::DuplicateCard(38))
+        );
+    }
+
+    #[test]
+    fn preflop_suited_flush_odds() {
+        // Suited hole cards flush when at least three of the eleven other cards of
+        // the suit come, or the board is five of another suit; nothing better than
+        // a flush fits beside either.
+        let odds = category_odds_exact_checked(&[12, 11], &[], &[]).unwrap();
+        assert_eq!(odds.total(), 2_118_760);
+        let flushes = 165 * 741 + 330 * 39 + 462 + 3 * 1287;
+        assert_eq!(odds.counts[Category::Flush as usize] + odds.counts[Category::StraightFlush as usize], flushes);
+        let p = odds.probability(Category::Flush) + odds.probability(Category::StraightFlush);
+        assert!((p - flushes as f64 / 2_118_760.0).abs() < 1e-12);
+        assert!(odds.at_least(Category::Flush) > p);
+    }
+
+    #[test]
+    fn seven_card_frequencies() {
+        let odds = category_odds_seven_cards();
+        assert_eq!(
+            odds.counts,
+            [23_294_460, 58_627_800, 31_433_400, 6_461_620, 6_180_020, 4_047_644, 3_473_184, 224_848, 41_584]
+        );
+        assert_eq!(odds.total(), 133_784_560);
+    }
+
+    #[test]
+    fn exact_when_runouts_fit_in_iters() {
+        let hole = [38, 37];
+        let board = [36, 31, 0];
+        let exact = category_odds_exact_checked(&hole, &board, &[]).unwrap();
+        assert_eq!(category_odds_checked(&hole, &board, &[], 1081, 1).unwrap(), exact);
+        let mc = category_odds_checked(&hole, &board, &[], 1080, 1).unwrap();
+        assert_eq!(mc, category_odds_mc_checked(&hole, &board, &[], 1080, 1).unwrap());
+        assert_eq!(category_odds_checked(&hole, &[], &[], 10_000, 1).unwrap().total(), 10_000);
+    }
+}
@@ -0,0 +1,431 @@
+//! Parallel (rayon) versions of the equity functions.
+//!
+//! Monte Carlo runs are split into `shards` independent streams: shard `i` runs
+//! `iters / shards` iterations (the first `iters % shards` shards get one more) seeded
+//! with `shard_seed(seed, i)`. Shards are merged in order, so the result depends only
+//! on `seed` and `shards`, never on the number of threads.
+//!
+//! Exact enumeration is split over runout classes; counts are
+//! identical to the sequential functions.
+
+use rayon::prelude::*;
+
+use super::*;
+
+/// Run `run(shard_iters, shard_seed)` for each shard in parallel; results in shard order.
+fn run_shards<T, F>(iters: u64, seed: u64, shards: usize, run: F) -> Result<Vec<T>, EquityError>
+where
+    T: Send,
+    F: Fn(u64, u64) -> Result<T, EquityError> + Sync,
+{
+    let shards = shards.max(1) as u64;
+    let base = iters / shards;
+    let extra = iters % shards;
+    (0..shards)
+        .into_par_iter()
+        .map(|i| run(base + u64::from(i < extra), shard_seed(seed, i)))
+        .collect()
+}
+
+fn merge_counts<T: Default + std::ops::AddAssign>(parts: Vec<T>) -> T {
+    let mut total = T::default();
+    for p in parts {
+        total += p;
+    }
+    total
+}
+
+fn merge_multiway<T: std::ops::AddAssign>(parts: Vec<Vec<T>>) -> Vec<T> {
+    let mut parts = parts.into_iter();
+    let mut total = parts.next().unwrap_or_default();
+    for p in parts {
+        for (a, b) in total.iter_mut().zip(p) {
+            *a += b;
+        }
+    }
+    total
+}
+
+/// Parallel [`equity_mc_vs_hand_checked_with_dead`] over `shards` deterministic streams.
+pub fn equity_mc_vs_hand_checked_par(
+    hero: &[u8; 2],
+    villain: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+) -> Result<EquityCounts, EquityError> {
+    equity_mc_vs_hand_sampled_checked_par(hero, villain, board, dead, iters, seed, shards, Sampling::Uniform)
+}
+
+/// Parallel [`equity_mc_vs_hand_sampled_checked`] over `shards` deterministic streams.
+#[allow(clippy::too_many_arguments)]
+pub fn equity_mc_vs_hand_sampled_checked_par(
+    hero: &[u8; 2],
+    villain: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+    sampling: Sampling,
+) -> Result<EquityCounts, EquityError> {
+    run_shards(iters, seed, shards, |n, s| {
+        equity_mc_vs_hand_sampled_checked(hero, villain, board, dead, n, s, sampling)
+    })
+    .map(merge_counts)
+}
+
+/// Parallel [`equity_mc_vs_random_checked_with_dead`] over `shards` deterministic streams.
+pub fn equity_mc_vs_random_checked_par(
+    hero: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+) -> Result<EquityCounts, EquityError> {
+    equity_mc_vs_random_sampled_checked_par(hero, board, dead, iters, seed, shards, Sampling::Uniform)
+}
+
+/// Parallel [`equity_mc_vs_random_sampled_checked`] over `shards` deterministic streams.
+pub fn equity_mc_vs_random_sampled_checked_par(
+    hero: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+    sampling: Sampling,
+) -> Result<EquityCounts, EquityError> {
+    run_shards(iters, seed, shards, |n, s| {
+        equity_mc_vs_random_sampled_checked(hero, board, dead, n, s, sampling)
+    })
+    .map(merge_counts)
+}
+
+/// Parallel [`equity_mc_multiway_checked_with_dead`] over `shards` deterministic streams.
+pub fn equity_mc_multiway_checked_par(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+) -> Result<MultiWayResult, EquityError> {
+    equity_mc_multiway_sampled_checked_par(hands, board, dead, iters, seed, shards, Sampling::Uniform)
+}
+
+/// Parallel [`equity_mc_multiway_sampled_checked`] over `shards` deterministic streams.
+pub fn equity_mc_multiway_sampled_checked_par(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+    sampling: Sampling,
+) -> Result<MultiWayResult, EquityError> {
+    run_shards(iters, seed, shards, |n, s| {
+        equity_mc_multiway_sampled_checked(hands, board, dead, n, s, sampling)
+    })
+    .map(merge_multiway)
+}
+
+/// Parallel [`equity_mc_multiway_pot_share_checked_with_dead`] over `shards` deterministic streams.
+pub fn equity_mc_multiway_pot_share_checked_par(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    run_shards(iters, seed, shards, |n, s| {
+        equity_mc_multiway_pot_share_checked_with_dead(hands, board, dead, n, s)
+    })
+    .map(merge_multiway)
+}
+
+/// Parallel [`equity_mc_vs_random_multiway_checked_with_dead`] over `shards` deterministic streams.
+pub fn equity_mc_vs_random_multiway_checked_par(
+    hero: &[u8; 2],
+    num_villains: usize,
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+) -> Result<EquityCounts, EquityError> {
+    equity_mc_vs_random_multiway_sampled_checked_par(hero, num_villains, board, dead, iters, seed, shards, Sampling::Uniform)
+}
+
+/// Parallel [`equity_mc_vs_random_multiway_sampled_checked`] over `shards` deterministic streams.
+#[allow(clippy::too_many_arguments)]
+pub fn equity_mc_vs_random_multiway_sampled_checked_par(
+    hero: &[u8; 2],
+    num_villains: usize,
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+    sampling: Sampling,
+) -> Result<EquityCounts, EquityError> {
+    run_shards(iters, seed, shards, |n, s| {
+        equity_mc_vs_random_multiway_sampled_checked(hero, num_villains, board, dead, n, s, sampling)
+    })
+    .map(merge_counts)
+}
+
+/// Parallel [`equity_mc_vs_random_multiway_pot_share_checked_with_dead`] over `shards`
+/// deterministic streams.
+pub fn equity_mc_vs_random_multiway_pot_share_checked_par(
+    hero: &[u8; 2],
+    num_villains: usize,
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+) -> Result<MultiWayCounts, EquityError> {
+    run_shards(iters, seed, shards, |n, s| {
+        equity_mc_vs_random_multiway_pot_share_checked_with_dead(hero, num_villains, board, dead, n, s)
+    })
+    .map(merge_counts)
+}
+
+/// Parallel [`equity_mc_ranges_multiway_checked_with_dead`] over `shards` deterministic streams.
+pub fn equity_mc_ranges_multiway_checked_par(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+) -> Result<MultiWayResult, EquityError> {
+    equity_mc_ranges_multiway_sampled_checked_par(hero, villains, board, dead, iters, seed, shards, Sampling::Uniform)
+}
+
+/// Parallel [`equity_mc_ranges_multiway_sampled_checked`] over `shards` deterministic streams.
+#[allow(clippy::too_many_arguments)]
+pub fn equity_mc_ranges_multiway_sampled_checked_par(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+    sampling: Sampling,
+) -> Result<MultiWayResult, EquityError> {
+    run_shards(iters, seed, shards, |n, s| {
+        equity_mc_ranges_multiway_sampled_checked(hero, villains, board, dead, n, s, sampling)
+    })
+    .map(merge_multiway)
+}
+
+/// Parallel [`equity_mc_ranges_multiway_pot_share_checked_with_dead`] over `shards`
+/// deterministic streams.
+pub fn equity_mc_ranges_multiway_pot_share_checked_par(
+    hero: &[u8; 2],
+    villains: &[Seat],
+    board: &[u8],
+    dead: &[u8],
+    iters: u64,
+    seed: u64,
+    shards: usize,
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    run_shards(iters, seed, shards, |n, s| {
+        equity_mc_ranges_multiway_pot_share_checked_with_dead(hero, villains, board, dead, n, s)
+    })
+    .map(merge_multiway)
+}
+
+/// Runout classes of an exact enumeration, collected so they can be split across threads.
+fn runout_classes(used: u64, board: &[u8]) -> Vec<([u8; 5], u64)> {
+    let mut classes = Vec::new();
+    for_each_runout_class(used, board, |board5, w| classes.push((*board5, w)));
+    classes
+}
+
+/// Parallel [`equity_exact_vs_hand_checked_with_dead`].
+pub fn equity_exact_vs_hand_checked_par(
+    hero: &[u8; 2],
+    villain: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<EquityCounts, EquityError> {
+    let used0 = validate_inputs(hero, Some(villain), board, dead)?;
+    check_deck(used0, 5 - board.len())?;
+
+    Ok(runout_classes(used0, board)
+        .par_iter()
+        .fold(EquityCounts::default, |mut counts, (board5, w)| {
+            bump_counts_weighted(&mut counts, eval_two_players_unchecked(hero, villain, board5), *w);
+            counts
+        })
+        .reduce(EquityCounts::default, |mut a, b| {
+            a += b;
+            a
+        }))
+}
+
+/// Parallel [`equity_exact_vs_random_checked_with_dead`].
+pub fn equity_exact_vs_random_checked_par(
+    hero: &[u8; 2],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<EquityCounts, EquityError> {
+    let used = validate_inputs(hero, None, board, dead)?;
+    check_deck(used, 7 - board.len())?;
+
+    Ok(runout_classes(used, board)
+        .par_iter()
+        .fold(EquityCounts::default, |mut counts, (board5, w)| {
+            score_random_villains(hero, board5, used, *w, &mut counts);
+            counts
+        })
+        .reduce(EquityCounts::default, |mut a, b| {
+            a += b;
+            a
+        }))
+}
+
+/// Parallel [`equity_exact_multiway_checked_with_dead`].
+pub fn equity_exact_multiway_checked_par(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<MultiWayResult, EquityError> {
+    equity_exact_multiway_pot_share_checked_par(hands, board, dead).map(raw_counts)
+}
+
+/// Parallel [`equity_exact_multiway_pot_share_checked_with_dead`].
+pub fn equity_exact_multiway_pot_share_checked_par(
+    hands: &[&[u8; 2]],
+    board: &[u8],
+    dead: &[u8],
+) -> Result<Vec<MultiWayCounts>, EquityError> {
+    let n = hands.len();
+    let used = validate_multiway(hands, board, dead)?;
+    check_deck(used, 5 - board.len())?;
+
+    let classes = runout_classes(used, board);
+    let chunk = (classes.len() / 256).max(64);
+    let parts: Vec<Vec<MultiWayCounts>> = classes
+        .par_chunks(chunk)
+        .map(|part| {
+            let mut results = vec![MultiWayCounts::default(); n];
+            let mut scores = vec![0u32; n];
+            for (board5, w) in part {
+                score_players(hands.iter().copied(), board5, &mut scores);
+                record_showdown_weighted(&mut results, &scores, *w);
+            }
+            results
+        })
+        .collect();
+    Ok(merge_multiway(parts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(threads: usize) -> rayon::ThreadPool {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn exact_par_matches_sequential() {
+        let hero = [12, 25];
+        let vill = [11, 24];
+        let flop = [0, 14, 28];
+
+        assert_eq!(
+            equity_exact_vs_hand_checked_par(&hero, &vill, &flop, &[3]).unwrap(),
+            equity_exact_vs_hand_checked_with_dead(&hero, &vill, &flop, &[3]).unwrap()
+        );
+        let turn = [0, 14, 28, 42];
+        assert_eq!(
+            equity_exact_vs_random_checked_par(&hero, &turn, &[]).unwrap(),
+            equity_exact_vs_random_checked(&hero, &turn).unwrap()
+        );
+        let q = [10, 23];
+        assert_eq!(
+            equity_exact_multiway_checked_par(&[&hero, &vill, &q], &flop, &[]).unwrap(),
+            equity_exact_multiway_checked(&[&hero, &vill, &q], &flop).unwrap()
+        );
+        assert_eq!(
+            equity_exact_multiway_pot_share_checked_par(&[&hero, &vill, &q], &flop, &[]).unwrap(),
+            equity_exact_multiway_pot_share_checked(&[&hero, &vill, &q], &flop).unwrap()
+        );
+    }
+
+    #[test]
+    fn mc_par_independent_of_thread_count() {
+        let hero = [12, 25];
+        let vill = [11, 24];
+        let run = || {
+            (
+                equity_mc_vs_hand_checked_par(&hero, &vill, &[], &[], 10_001, 9, 7).unwrap(),
+                equity_mc_vs_random_multiway_checked_par(&hero, 3, &[], &[], 5_000, 9, 5).unwrap(),
+                equity_mc_ranges_multiway_checked_par(&hero, &[Seat::Random], &[], &[], 5_000, 9, 3)
+                    .unwrap(),
+            )
+        };
+        let one = pool(1).install(run);
+        let four = pool(4).install(run);
+        assert_eq!(one, four);
+        assert_eq!(one.0.total(), 10_001);
+    }
+
+    #[test]
+    fn mc_par_single_shard_is_sequential_run() {
+        let hero = [12, 25];
+        let seq = equity_mc_vs_random_checked(&hero, &[], 2_000, shard_seed(1, 0)).unwrap();
+        let par = equity_mc_vs_random_checked_par(&hero, &[], &[], 2_000, 1, 1).unwrap();
+        assert_eq!(seq, par);
+
+        let flop = [0, 14, 28];
+        let seq = equity_mc_vs_hand_sampled_checked(&hero, &[11, 24], &flop, &[], 2_070, shard_seed(1, 0), Sampling::Stratified)
+            .unwrap();
+        let par =
+            equity_mc_vs_hand_sampled_checked_par(&hero, &[11, 24], &flop, &[], 2_070, 1, 1, Sampling::Stratified).unwrap();
+        assert_eq!(seq, par);
+    }
+
+    #[test]
+    fn mc_pot_share_par_single_shard_is_sequential_run() {
+        let hero = [12, 25];
+        let vill = [11, 24];
+        let s0 = shard_seed(3, 0);
+
+        let seq = equity_mc_multiway_pot_share_checked(&[&hero, &vill], &[], 1_500, s0).unwrap();
+        let par = equity_mc_multiway_pot_share_checked_par(&[&hero, &vill], &[], &[], 1_500, 3, 1).unwrap();
+        assert_eq!(seq, par);
+
+        let seq = equity_mc_vs_random_multiway_pot_share_checked(&hero, 2, &[], 1_500, s0).unwrap();
+        let par = equity_mc_vs_random_multiway_pot_share_checked_par(&hero, 2, &[], &[], 1_500, 3, 1).unwrap();
+        assert_eq!(seq, par);
+
+        let seats = [Seat::Hand(vill), Seat::Random];
+        let seq = equity_mc_ranges_multiway_pot_share_checked(&hero, &seats, &[], 1_500, s0).unwrap();
+        let par = equity_mc_ranges_multiway_pot_share_checked_par(&hero, &seats, &[], &[], 1_500, 3, 1).unwrap();
+        assert_eq!(seq, par);
+
+        let sharded = equity_mc_ranges_multiway_pot_share_checked_par(&hero, &seats, &[], &[], 1_501, 3, 4).unwrap();
+        assert!(sharded.iter().all(|r| r.counts.total() == 1_501));
+    }
+
+    #[test]
+    fn par_propagates_errors() {
+        let res = equity_mc_vs_hand_checked_par(&[0, 1], &[1, 2], &[], &[], 100, 0, 4);
+        assert_eq!(res, Err(EquityError::DuplicateCard(1)));
+    }
+}
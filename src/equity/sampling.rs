@@ -0,0 +1,306 @@
+//! Runout sampling strategies for Monte Carlo equity.
+//!
+//! Every strategy is unbiased: each runout has the same probability (or expected
+//! count) as under uniform sampling. The non-uniform ones spread a run's samples
+//! more evenly over the runouts, which lowers the variance of the estimate for a
+//! given number of iterations.
+
+use super::{fill_remaining_cards, sample_distinct_cards, CardSampler52};
+
+/// How Monte Carlo runouts (the missing board cards) are drawn.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum Sampling {
+    /// Independent uniform runouts.
+    #[default]
+    Uniform,
+    /// The last two missing cards (turn and river) cycle through every pair of
+    /// remaining cards in shuffled order; earlier cards are drawn uniformly.
+    Stratified,
+    /// One random start, then evenly spaced steps through the list of all runouts.
+    Systematic,
+    /// Randomly shifted low-discrepancy (Kronecker) sequence mapped to runouts.
+    QuasiRandom,
+}
+
+const fn build_binom() -> [[u64; 6]; 53] {
+    let mut t = [[0u64; 6]; 53];
+    let mut n = 0usize;
+    while n < 53 {
+        t[n][0] = 1;
+        let mut k = 1usize;
+        while k < 6 && k <= n {
+            t[n][k] = t[n - 1][k - 1] + t[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    t
+}
+
+/// `BINOM[n][k]` = n choose k, for k <= 5.
+pub(super) const BINOM: [[u64; 6]; 53] = build_binom();
+
+/// Uniform f64 in [0, 1).
+#[inline(always)]
+fn unit_f64(s: &mut CardSampler52) -> f64 {
+    (s.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+/// Draws the missing board cards according to a [`Sampling`] strategy.
+pub(super) struct RunoutSampler {
+    kind: Sampling,
+    missing: usize,
+    /// Draws are positions among the cards left at draw time (see `positional`).
+    positional: bool,
+    /// Cards not in the known set, ascending.
+    deck: [u8; 52],
+    m: usize,
+    // Stratified: every turn/river stratum, visited in a fresh shuffle each cycle.
+    strata: Vec<[u8; 2]>,
+    order: Vec<u32>,
+    pos: usize,
+    // Systematic: position i maps to runout floor((i + start) * total / iters).
+    total: u64,
+    step: f64,
+    start: f64,
+    i: u64,
+    // QuasiRandom: current point of the shifted Kronecker sequence.
+    alpha: [f64; 5],
+    point: [f64; 5],
+}
+
+impl RunoutSampler {
+    /// `used0` is every card known before the runout is drawn. `Uniform` draws no
+    /// randomness here, so it reproduces the plain sampler's stream exactly.
+    pub(super) fn new(
+        kind: Sampling,
+        used0: u64,
+        missing: usize,
+        iters: u64,
+        s: &mut CardSampler52,
+    ) -> Self {
+        let mut deck = [0u8; 52];
+        let mut m = 0usize;
+        for id in 0u8..52 {
+            if (used0 & (1u64 << id)) == 0 {
+                deck[m] = id;
+                m += 1;
+            }
+        }
+
+        let mut r = Self {
+            kind,
+            missing,
+            positional: false,
+            deck,
+            m,
+            strata: Vec::new(),
+            order: Vec::new(),
+            pos: 0,
+            total: 0,
+            step: 0.0,
+            start: 0.0,
+            i: 0,
+            alpha: [0.0; 5],
+            point: [0.0; 5],
+        };
+        if missing == 0 {
+            return r;
+        }
+
+        match kind {
+            Sampling::Uniform => {}
+            Sampling::Stratified => {
+                let cards = &deck[..m];
+                if missing == 1 {
+                    r.strata = cards.iter().map(|&c| [c, c]).collect();
+                } else {
+                    for (i, &a) in cards.iter().enumerate() {
+                        for &b in &cards[i + 1..] {
+                            r.strata.push([a, b]);
+                        }
+                    }
+                }
+                r.order = (0..r.strata.len() as u32).collect();
+                r.pos = r.order.len();
+            }
+            Sampling::Systematic => {
+                r.total = BINOM[m][missing];
+                r.step = r.total as f64 / iters.max(1) as f64;
+                r.start = unit_f64(s);
+            }
+            Sampling::QuasiRandom => {
+                // R_d sequence: alpha_j = phi_d^-(j+1), phi_d the positive root of x^(d+1) = x + 1.
+                let d = missing as i32;
+                let mut phi = 2.0f64;
+                for _ in 0..30 {
+                    phi -= (phi.powi(d + 1) - phi - 1.0) / ((d + 1) as f64 * phi.powi(d) - 1.0);
+                }
+                for j in 0..missing {
+                    r.alpha[j] = phi.powi(-(j as i32 + 1));
+                    r.point[j] = unit_f64(s);
+                }
+            }
+        }
+        r
+    }
+
+    /// Sampler for runouts drawn after a random deal that always leaves `live` cards:
+    /// strategies pick positions among the cards left at draw time, so every runout
+    /// stays uniform over whatever the deal left and `next` may run last.
+    pub(super) fn positional(kind: Sampling, live: usize, missing: usize, iters: u64, s: &mut CardSampler52) -> Self {
+        let mut r = Self::new(kind, !0u64 << live, missing, iters, s);
+        r.positional = true;
+        r
+    }
+
+    #[inline(always)]
+    pub(super) fn is_uniform(&self) -> bool {
+        self.kind == Sampling::Uniform
+    }
+
+    /// Draw `out.len() == missing` cards not in `used`, marking them used.
+    /// Non-uniform strategies draw from the cards outside `used0` and must run first,
+    /// unless the sampler is positional.
+    #[inline]
+    pub(super) fn next(&mut self, s: &mut CardSampler52, used: &mut u64, out: &mut [u8]) {
+        debug_assert_eq!(out.len(), self.missing);
+        if self.missing == 0 {
+            return;
+        }
+        if self.positional && !self.is_uniform() {
+            // Draws are positions in `0..m`; block the rest so extra cards stay in range.
+            let mut taken = !0u64 << self.m;
+            self.draw(s, &mut taken, out);
+            let mut live = [0u8; 52];
+            let n = fill_remaining_cards(*used, &mut live);
+            debug_assert_eq!(n, self.m);
+            for c in out.iter_mut() {
+                *c = live[*c as usize];
+                *used |= 1u64 << *c;
+            }
+            return;
+        }
+        self.draw(s, used, out);
+    }
+
+    #[inline]
+    fn draw(&mut self, s: &mut CardSampler52, used: &mut u64, out: &mut [u8]) {
+        match self.kind {
+            Sampling::Uniform => {
+                let _ = sample_distinct_cards(s, used, out);
+            }
+            Sampling::Stratified => {
+                if self.pos == self.order.len() {
+                    for i in (1..self.order.len()).rev() {
+                        let j = ((s.next_u64() >> 32) * (i as u64 + 1)) >> 32;
+                        self.order.swap(i, j as usize);
+                    }
+                    self.pos = 0;
+                }
+                let st = self.strata[self.order[self.pos] as usize];
+                self.pos += 1;
+
+                let k = self.missing;
+                if k == 1 {
+                    out[0] = st[0];
+                    *used |= 1u64 << st[0];
+                } else {
+                    out[k - 2] = st[0];
+                    out[k - 1] = st[1];
+                    *used |= (1u64 << st[0]) | (1u64 << st[1]);
+                    let _ = sample_distinct_cards(s, used, &mut out[..k - 2]);
+                }
+            }
+            Sampling::Systematic => {
+                let pos = ((self.i as f64 + self.start) * self.step) as u64;
+                self.i += 1;
+                // Colex unranking of runout index `pos % total`.
+                let mut rank = pos % self.total;
+                let mut c = self.m;
+                for k in (1..=self.missing).rev() {
+                    c -= 1;
+                    while BINOM[c][k] > rank {
+                        c -= 1;
+                    }
+                    rank -= BINOM[c][k];
+                    let id = self.deck[c];
+                    out[k - 1] = id;
+                    *used |= 1u64 << id;
+                }
+            }
+            Sampling::QuasiRandom => {
+                // Map each coordinate to one card drawn without replacement,
+                // keeping the remaining cards in order.
+                let mut deck = self.deck;
+                let mut left = self.m;
+                for (j, slot) in out.iter_mut().enumerate() {
+                    let x = self.point[j];
+                    let idx = ((x * left as f64) as usize).min(left - 1);
+                    let id = deck[idx];
+                    deck.copy_within(idx + 1..left, idx);
+                    left -= 1;
+                    *slot = id;
+                    *used |= 1u64 << id;
+
+                    let next = x + self.alpha[j];
+                    self.point[j] = if next >= 1.0 { next - 1.0 } else { next };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Count how often each runout is drawn over `iters` samples.
+    fn histogram(kind: Sampling, used0: u64, missing: usize, iters: u64) -> Vec<u64> {
+        let mut s = CardSampler52::new(17);
+        let mut r = RunoutSampler::new(kind, used0, missing, iters, &mut s);
+        let mut counts = vec![0u64; 52 * 52];
+        let mut out = [0u8; 2];
+        for _ in 0..iters {
+            let mut used = used0;
+            r.next(&mut s, &mut used, &mut out[..missing]);
+            assert_eq!((used & !used0).count_ones() as usize, missing);
+            let (a, b) = (out[0].min(out[missing - 1]), out[0].max(out[missing - 1]));
+            counts[a as usize * 52 + b as usize] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn stratified_and_systematic_cover_every_runout_evenly() {
+        // 48 cards left, 2 missing: 1128 runouts, each hit exactly twice.
+        let used0 = (1u64 << 4) - 1;
+        for kind in [Sampling::Stratified, Sampling::Systematic] {
+            let h = histogram(kind, used0, 2, 2 * 1128);
+            assert_eq!(h.iter().filter(|&&c| c > 0).count(), 1128, "{:?}", kind);
+            assert!(h.iter().all(|&c| c == 0 || c == 2), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn quasi_random_draws_valid_runouts() {
+        let used0 = (1u64 << 7) - 1;
+        let h = histogram(Sampling::QuasiRandom, used0, 2, 20_000);
+        assert_eq!(h.iter().sum::<u64>(), 20_000);
+        // Low discrepancy: no runout is drawn far more often than average (~22).
+        assert!(h.iter().all(|&c| c < 60));
+    }
+
+    #[test]
+    fn systematic_unranks_five_card_runouts() {
+        let mut s = CardSampler52::new(3);
+        let mut r = RunoutSampler::new(Sampling::Systematic, 0, 5, 1000, &mut s);
+        for _ in 0..1000 {
+            let mut used = 0u64;
+            let mut out = [0u8; 5];
+            r.next(&mut s, &mut used, &mut out);
+            assert_eq!(used.count_ones(), 5);
+            assert!(out.iter().all(|&c| c < 52));
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! 13×13 equity grid: a range's equity against another range, by starting-hand class.
+//!
+//! On a 3-5 card board every runout goes through the river sort-and-sweep once,
+//! which gives each hero combo the villain weight it beats, ties and loses to
+//! with card removal. Sums over runouts give each combo's equity; a class's equity
+//! pools its combos by hero weight and live villain weight, like
+//! [`river_range_equity`](crate::river::river_range_equity) does for a whole range.
+
+use std::fmt;
+use std::fmt::Write as _;
+
+use crate::equity::{enumerate_board_completions, fill_remaining_cards, validate_board, EquityError};
+use crate::hand_class::HandClass;
+use crate::range::Range;
+use crate::river::river_showdown;
+
+/// One hero combo's equity.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ComboEquity {
+    pub hand: [u8; 2],
+    /// Weight in hero's range.
+    pub weight: f64,
+    pub equity: f64,
+}
+
+/// Hero's combos of one class.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridCell {
+    pub class: HandClass,
+    /// Hero weight in the class.
+    pub weight: f64,
+    pub equity: f64,
+    /// Combos in range order.
+    pub combos: Vec<ComboEquity>,
+}
+
+/// Equity of hero's range against villain's on a board, per starting-hand class.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EquityGrid {
+    cells: Vec<Option<GridCell>>,
+    equity: f64,
+}
+
+impl EquityGrid {
+    /// Grid of `hero` against `villain` over every runout of a 3-5 card `board`.
+    /// Combos of either range touching the board or `dead` are left out.
+    pub fn new(hero: &Range, villain: &Range, board: &[u8], dead: &[u8]) -> Result<Self, EquityError> {
+        let used = validate_board(board, dead)?;
+        let hero = hero.live(used);
+        let villain = villain.live(used);
+        if hero.is_empty() || villain.is_empty() {
+            return Err(EquityError::EmptyRange);
+        }
+
+        // Pot share and villain weight faced, per hero combo, over all runouts.
+        let mut share = vec![0.0f64; hero.len()];
+        let mut faced = vec![0.0f64; hero.len()];
+        let mut rem = [0u8; 52];
+        let n = fill_remaining_cards(used, &mut rem);
+        enumerate_board_completions(&rem[..n], board, 5 - board.len(), |board5| {
+            let rows = river_showdown(&hero, &villain, &board5).expect("board is validated");
+            for (i, row) in rows.iter().enumerate() {
+                share[i] += row.win + 0.5 * row.tie;
+                faced[i] += row.total();
+            }
+        });
+
+        let mut cells: Vec<Option<GridCell>> = vec![None; HandClass::COUNT];
+        let mut sums = vec![(0.0f64, 0.0f64); HandClass::COUNT];
+        let (mut num, mut den) = (0.0, 0.0);
+        for (i, c) in hero.combos().iter().enumerate() {
+            if faced[i] <= 0.0 {
+                continue;
+            }
+            let class = HandClass::from_hand(c.hand);
+            let cell = cells[class.index()].get_or_insert(GridCell {
+                class,
+                weight: 0.0,
+                equity: 0.0,
+                combos: Vec::new(),
+            });
+            cell.weight += c.weight;
+            cell.combos.push(ComboEquity { hand: c.hand, weight: c.weight, equity: share[i] / faced[i] });
+            let s = &mut sums[class.index()];
+            s.0 += c.weight * share[i];
+            s.1 += c.weight * faced[i];
+            num += c.weight * share[i];
+            den += c.weight * faced[i];
+        }
+        for (cell, &(s, f)) in cells.iter_mut().zip(&sums) {
+            if let Some(cell) = cell {
+                cell.equity = if f > 0.0 { s / f } else { 0.0 };
+            }
+        }
+        if den <= 0.0 {
+            return Err(EquityError::EmptyRange);
+        }
+        Ok(Self { cells, equity: num / den })
+    }
+
+    /// Hero's whole-range equity.
+    pub fn equity(&self) -> f64 {
+        self.equity
+    }
+
+    /// Cell of a class, if hero holds it on this board.
+    pub fn cell(&self, class: HandClass) -> Option<&GridCell> {
+        self.cells[class.index()].as_ref()
+    }
+
+    /// Cell at chart `row` and `col` (0 = Ace).
+    pub fn get(&self, row: usize, col: usize) -> Option<&GridCell> {
+        self.cells.get(row * 13 + col)?.as_ref()
+    }
+
+    /// Cells hero holds, in chart order.
+    pub fn cells(&self) -> impl Iterator<Item = &GridCell> {
+        self.cells.iter().flatten()
+    }
+
+    /// Equity per chart position, for heatmaps.
+    pub fn matrix(&self) -> [[Option<f64>; 13]; 13] {
+        let mut m = [[None; 13]; 13];
+        for c in self.cells() {
+            m[c.class.row()][c.class.col()] = Some(c.equity);
+        }
+        m
+    }
+
+    /// HTML table of the grid, cells shaded from red (0%) to green (100%).
+    pub fn to_html(&self) -> String {
+        let mut s = String::from("<table class=\"equity-grid\">\n");
+        for row in 0..13 {
+            s.push_str("<tr>");
+            for col in 0..13 {
+                let class = HandClass::from_index(row * 13 + col).expect("index below 169");
+                match self.get(row, col) {
+                    Some(c) => {
+                        let n = c.combos.len();
+                        let _ = write!(
+                            s,
+                            "<td style=\"background:hsl({:.0},70%,70%)\" title=\"{}: {} combo{}\">{}<br>{:.1}</td>",
+                            c.equity * 120.0,
+                            class,
+                            n,
+                            if n == 1 { "" } else { "s" },
+                            class,
+                            c.equity * 100.0
+                        );
+                    }
+                    None => {
+                        let _ = write!(s, "<td>{}</td>", class);
+                    }
+                }
+            }
+            s.push_str("</tr>\n");
+        }
+        s.push_str("</table>\n");
+        s
+    }
+}
+
+/// Text grid: one line per chart row, equity in percent, `-` where hero holds nothing.
+impl fmt::Display for EquityGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..13 {
+            for col in 0..13 {
+                if col > 0 {
+                    f.write_str(" ")?;
+                }
+                let class = HandClass::from_index(row * 13 + col).expect("index below 169");
+                match self.get(row, col) {
+                    Some(c) => write!(f, "{:>3} {:>5.1}", class.to_string(), c.equity * 100.0)?,
+                    None => write!(f, "{:>3} {:>5}", class.to_string(), "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equity::equity_exact_vs_hand_checked;
+    use crate::river::river_range_equity;
+
+    #[test]
+    fn grid_matches_river_and_exact_equity() {
+        // River: the grid's overall equity is the sweep's range equity.
+        let board = [0, 14, 28, 42, 5];
+        let hero = Range::random();
+        let villain = Range::from_hands(&[[12, 25], [11, 24], [3, 16], [9, 48]]).unwrap();
+        let g = EquityGrid::new(&hero, &villain, &board, &[]).unwrap();
+        let hero_live = hero.live(board.iter().fold(0, |m, &c| m | 1u64 << c));
+        let want = river_range_equity(&hero_live, &villain, &board).unwrap();
+        assert!((g.equity() - want).abs() < 1e-12);
+        assert_eq!(g.cells().map(|c| c.combos.len()).sum::<usize>(), 47 * 46 / 2);
+        assert!(g.to_html().contains("title=\"AKs: 4 combos\""));
+
+        // Flop, one villain hand: each combo's equity is the exact heads-up equity.
+        let board = [12, 30, 7];
+        let villain = Range::from_hands(&[[11, 24]]).unwrap();
+        let hero = Range::from_hands(&[[25, 38], [51, 50], [49, 10], [24, 37]]).unwrap();
+        let g = EquityGrid::new(&hero, &villain, &board, &[]).unwrap();
+        for cell in g.cells() {
+            for c in &cell.combos {
+                let e = equity_exact_vs_hand_checked(&c.hand, &[11, 24], &board).unwrap().equity();
+                assert!((c.equity - e).abs() < 1e-12, "{:?}", c.hand);
+            }
+        }
+        // Kd Kh shares a card with villain's kings and is left out of its class.
+        let aa = g.cell("AA".parse().unwrap()).unwrap();
+        assert_eq!(aa.combos.len(), 1);
+        assert!(g.cell("KK".parse().unwrap()).is_none());
+        assert_eq!(g.cells().count(), 3);
+
+        let m = g.matrix();
+        assert_eq!(m[0][0], Some(aa.equity));
+        assert_eq!(m[1][1], None);
+        let text = g.to_string();
+        assert_eq!(text.lines().count(), 13);
+        assert!(text.lines().next().unwrap().starts_with(" AA"));
+        let html = g.to_html();
+        assert!(html.contains("title=\"AA: 1 combo\""));
+        assert!(!html.contains("1 combos"));
+
+        assert_eq!(
+            EquityGrid::new(&hero, &Range::from_hands(&[[12, 0]]).unwrap(), &board, &[]),
+            Err(EquityError::EmptyRange)
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! The 169 starting-hand classes (pairs, suited and offsuit hands).
+//!
+//! Classes are laid out like the usual 13×13 chart: rows and columns run from
+//! Ace down to Two, pairs sit on the diagonal, suited hands above it and offsuit
+//! hands below it. `index = row * 13 + col`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::card::Rank;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct HandClass(u8);
+
+impl HandClass {
+    pub const COUNT: usize = 169;
+
+    /// Class at `index` (0..169) of the 13×13 chart.
+    #[inline(always)]
+    pub fn from_index(index: usize) -> Option<Self> {
+        (index < Self::COUNT).then_some(Self(index as u8))
+    }
+
+    /// Class of two ranks; `suited` is ignored for pairs.
+    pub fn from_ranks(a: Rank, b: Rank, suited: bool) -> Self {
+        let (hi, lo) = if a >= b { (a, b) } else { (b, a) };
+        let (hr, lr) = (12 - hi.idx(), 12 - lo.idx());
+        if suited {
+            Self(hr * 13 + lr)
+        } else {
+            Self(lr * 13 + hr)
+        }
+    }
+
+    /// Class of a two-card holding (card ids 0..51).
+    #[inline(always)]
+    pub fn from_hand(hand: [u8; 2]) -> Self {
+        let (r0, r1) = (hand[0] % 13, hand[1] % 13);
+        let (hi, lo) = (r0.max(r1), r0.min(r1));
+        let suited = hand[0] / 13 == hand[1] / 13;
+        if suited {
+            Self((12 - hi) * 13 + (12 - lo))
+        } else {
+            Self((12 - lo) * 13 + (12 - hi))
+        }
+    }
+
+    /// All 169 classes in chart order.
+    pub fn all() -> impl Iterator<Item = HandClass> {
+        (0..Self::COUNT as u8).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Chart row (0 = Ace).
+    #[inline(always)]
+    pub fn row(self) -> usize {
+        self.0 as usize / 13
+    }
+
+    /// Chart column (0 = Ace).
+    #[inline(always)]
+    pub fn col(self) -> usize {
+        self.0 as usize % 13
+    }
+
+    pub fn high(self) -> Rank {
+        Rank::from_u8(12 - self.row().min(self.col()) as u8)
+    }
+
+    pub fn low(self) -> Rank {
+        Rank::from_u8(12 - self.row().max(self.col()) as u8)
+    }
+
+    #[inline(always)]
+    pub fn is_pair(self) -> bool {
+        self.row() == self.col()
+    }
+
+    #[inline(always)]
+    pub fn is_suited(self) -> bool {
+        self.row() < self.col()
+    }
+
+    /// 6 for pairs, 4 for suited and 12 for offsuit hands.
+    #[inline(always)]
+    pub fn num_combos(self) -> usize {
+        if self.is_pair() {
+            6
+        } else if self.is_suited() {
+            4
+        } else {
+            12
+        }
+    }
+
+    /// Every holding in the class, lower card id first.
+    pub fn combos(self) -> Vec<[u8; 2]> {
+        let (hi, lo) = (self.high().idx(), self.low().idx());
+        let mut out = Vec::with_capacity(self.num_combos());
+        for s1 in 0..4u8 {
+            for s2 in 0..4u8 {
+                let keep = if self.is_pair() {
+                    s1 < s2
+                } else if self.is_suited() {
+                    s1 == s2
+                } else {
+                    s1 != s2
+                };
+                if keep {
+                    let (a, b) = (s1 * 13 + hi, s2 * 13 + lo);
+                    out.push([a.min(b), a.max(b)]);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_pair() {
+            write!(f, "{}{}", self.high(), self.low())
+        } else {
+            let kind = if self.is_suited() { 's' } else { 'o' };
+            write!(f, "{}{}{}", self.high(), self.low(), kind)
+        }
+    }
+}
+
+impl FromStr for HandClass {
+    type Err = String;
+
+    /// Parses "AA", "AKs" or "AKo" (ranks in either order).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.trim().chars().collect();
+        if chars.len() != 2 && chars.len() != 3 {
+            return Err(format!("Invalid hand class: '{}' (expected e.g. 'AA', 'AKs', 'AKo')", s));
+        }
+        let a = Rank::from_str(&chars[0].to_string())?;
+        let b = Rank::from_str(&chars[1].to_string())?;
+        let suited = match (chars.get(2), a == b) {
+            (None, true) => false,
+            (Some('s') | Some('S'), false) => true,
+            (Some('o') | Some('O'), false) => false,
+            _ => {
+                return Err(format!(
+                    "Invalid hand class: '{}' (pairs take no suffix, other hands need 's' or 'o')",
+                    s
+                ))
+            }
+        };
+        Ok(Self::from_ranks(a, b, suited))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chart_layout_and_names() {
+        assert_eq!(HandClass::from_index(0).unwrap().to_string(), "AA");
+        assert_eq!(HandClass::from_index(1).unwrap().to_string(), "AKs");
+        assert_eq!(HandClass::from_index(13).unwrap().to_string(), "AKo");
+        assert_eq!(HandClass::from_index(168).unwrap().to_string(), "22");
+        assert!(HandClass::from_index(169).is_none());
+        for c in HandClass::all() {
+            assert_eq!(c.to_string().parse::<HandClass>().unwrap(), c);
+        }
+        assert_eq!("KAs".parse::<HandClass>().unwrap().to_string(), "AKs");
+        assert!("AKx".parse::<HandClass>().is_err());
+        assert!("AAs".parse::<HandClass>().is_err());
+        assert!("AK".parse::<HandClass>().is_err());
+    }
+
+    #[test]
+    fn combos_cover_every_holding_once() {
+        let mut seen = vec![false; 52 * 52];
+        let mut total = 0;
+        for c in HandClass::all() {
+            let combos = c.combos();
+            assert_eq!(combos.len(), c.num_combos());
+            for h in combos {
+                assert_eq!(HandClass::from_hand(h), c);
+                assert_eq!(HandClass::from_hand([h[1], h[0]]), c);
+                assert!(!seen[h[0] as usize * 52 + h[1] as usize]);
+                seen[h[0] as usize * 52 + h[1] as usize] = true;
+                total += 1;
+            }
+        }
+        assert_eq!(total, 1326);
+    }
+}
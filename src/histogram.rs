@@ -0,0 +1,191 @@
+//! Distributions of hero's river equity over the runouts of the current board.
+//!
+//! On the flop every turn and river pair is dealt, hero's river equity against
+//! the opponent range is computed on each complete board, and the values are
+//! binned. Each runout goes through the river sort-and-sweep once for the whole
+//! hero range, so a range costs about as much as a single hand.
+
+use crate::equity::{enumerate_board_completions, fill_remaining_cards, validate_board, validate_inputs, EquityError};
+use crate::range::Range;
+use crate::river::river_showdown;
+
+/// Distribution of one holding's river equity, over equal-width bins of [0,1].
+#[derive(Clone, Debug, PartialEq)]
+pub struct EquityHistogram {
+    pub hand: [u8; 2],
+    /// Share of runouts per bin, each weighted by the villain weight it leaves
+    /// live; sums to 1.
+    pub bins: Vec<f64>,
+    /// Mean river equity (the equity on the current street).
+    pub mean: f64,
+}
+
+impl EquityHistogram {
+    /// Bin of an equity value; 1.0 falls in the last bin.
+    #[inline(always)]
+    pub fn bin_of(equity: f64, bins: usize) -> usize {
+        ((equity * bins as f64) as usize).min(bins - 1)
+    }
+}
+
+/// Histogram of `hero`'s river equity against a random hand over all runouts of
+/// a 3-5 card `board`.
+pub fn equity_histogram(hero: &[u8; 2], board: &[u8], dead: &[u8], bins: usize) -> Result<EquityHistogram, EquityError> {
+    if board.len() < 3 {
+        return Err(EquityError::TooFewBoardCards(board.len()));
+    }
+    validate_inputs(hero, None, board, dead)?;
+    let hero_range = Range::from_hands(&[*hero])?;
+    let mut h = equity_histograms(&hero_range, &Range::random(), board, dead, bins)?.remove(0);
+    h.hand = *hero;
+    Ok(h)
+}
+
+/// Histograms of every combo of `hero` against `villain` over all runouts of a
+/// 3-5 card `board`.
+///
+/// Returns one histogram per hero combo with positive weight that avoids the
+/// board and `dead`, in range order. Villain combos are weighted by their range
+/// weight and dropped where they collide with a card already dealt; each runout
+/// counts by the villain weight it leaves live, as in [`crate::EquityGrid`].
+pub fn equity_histograms(
+    hero: &Range,
+    villain: &Range,
+    board: &[u8],
+    dead: &[u8],
+    bins: usize,
+) -> Result<Vec<EquityHistogram>, EquityError> {
+    if bins == 0 {
+        return Err(EquityError::InvalidBinCount);
+    }
+    let used = validate_board(board, dead)?;
+    let hero = hero.live(used);
+    let villain = villain.live(used);
+    if villain.is_empty() {
+        return Err(EquityError::EmptyRange);
+    }
+
+    let mut weights = vec![vec![0.0f64; bins]; hero.len()];
+    let mut sums = vec![0.0f64; hero.len()];
+    let mut rem = [0u8; 52];
+    let n = fill_remaining_cards(used, &mut rem);
+    enumerate_board_completions(&rem[..n], board, 5 - board.len(), |board5| {
+        let rows = river_showdown(&hero, &villain, &board5).expect("board is validated");
+        for (i, row) in rows.iter().enumerate() {
+            // Zero when the runout hits the combo or blocks all of villain's range.
+            let w = row.total();
+            if w > 0.0 {
+                weights[i][EquityHistogram::bin_of(row.equity(), bins)] += w;
+                sums[i] += row.win + 0.5 * row.tie;
+            }
+        }
+    });
+
+    Ok(hero
+        .combos()
+        .iter()
+        .zip(weights.iter().zip(&sums))
+        .map(|(c, (weights, &sum))| {
+            let total = weights.iter().sum::<f64>();
+            let total = if total > 0.0 { total } else { 1.0 };
+            EquityHistogram {
+                hand: c.hand,
+                bins: weights.iter().map(|&w| w / total).collect(),
+                mean: sum / total,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equity::{equity_exact_vs_hand_checked, equity_exact_vs_random_checked};
+    use crate::river::river_range_equity;
+
+    #[test]
+    fn turn_histogram_matches_river_equities() {
+        let hero = [12, 11]; // Ac Kc
+        let board = [10, 9, 23, 36];
+        let h = equity_histogram(&hero, &board, &[], 10).unwrap();
+        assert_eq!(h.hand, hero);
+
+        let mut expect = vec![0.0; 10];
+        let mut rivers = 0.0;
+        let hero_range = Range::from_hands(&[hero]).unwrap();
+        for r in 0u8..52 {
+            if board.contains(&r) || hero.contains(&r) {
+                continue;
+            }
+            let b5 = [board[0], board[1], board[2], board[3], r];
+            let e = river_range_equity(&hero_range, &Range::random(), &b5).unwrap();
+            expect[EquityHistogram::bin_of(e, 10)] += 1.0;
+            rivers += 1.0;
+        }
+        for (got, want) in h.bins.iter().zip(&expect) {
+            assert!((got - want / rivers).abs() < 1e-12);
+        }
+        // Every runout sees the same number of villain holdings, so the mean is
+        // the exact equity on the turn.
+        let exact = equity_exact_vs_random_checked(&hero, &board).unwrap().equity();
+        assert!((h.mean - exact).abs() < 1e-12);
+    }
+
+    #[test]
+    fn range_histograms_match_single_hands() {
+        let board = [0, 14, 28, 42];
+        let dead = [5];
+        let hero = Range::from_hands(&[[12, 25], [0, 1], [30, 31], [5, 6]]).unwrap();
+        let hs = equity_histograms(&hero, &Range::random(), &board, &dead, 8).unwrap();
+        // [0, 1] touches the board and [5, 6] a dead card.
+        assert_eq!(hs.len(), 2);
+        for h in &hs {
+            assert_eq!(h, &equity_histogram(&h.hand, &board, &dead, 8).unwrap());
+            assert!((h.bins.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+
+        assert_eq!(
+            equity_histogram(&[12, 25], &[0, 14], &[], 8),
+            Err(EquityError::TooFewBoardCards(2))
+        );
+        assert_eq!(
+            equity_histogram(&[12, 0], &board, &[], 8),
+            Err(EquityError::DuplicateCard(0))
+        );
+        assert_eq!(
+            equity_histogram(&[12, 25], &board, &[], 0),
+            Err(EquityError::InvalidBinCount)
+        );
+    }
+
+    #[test]
+    fn narrow_range_weights_runouts_by_live_villain_weight() {
+        // Kings and a weighted AhKh: runouts with an ace or king block part of the range.
+        let hero = [12, 25]; // Ac Ad
+        let flop = [0, 14, 28];
+        let villain_hands = [([11, 24], 1.0), ([38, 50], 3.0)];
+        let mut villain = Range::new();
+        for &(hand, w) in &villain_hands {
+            villain.add(hand, w).unwrap();
+        }
+        let h = equity_histograms(&Range::from_hands(&[hero]).unwrap(), &villain, &flop, &[], 20)
+            .unwrap()
+            .remove(0);
+
+        let (mut share, mut total) = (0.0, 0.0);
+        for &(hand, w) in &villain_hands {
+            let c = equity_exact_vs_hand_checked(&hero, &hand, &flop).unwrap();
+            share += w * (c.win as f64 + 0.5 * c.tie as f64);
+            total += w * c.total() as f64;
+        }
+        assert!((h.mean - share / total).abs() < 1e-12, "{} vs {}", h.mean, share / total);
+        assert!((h.bins.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        let binned: f64 = h
+            .bins
+            .iter()
+            .enumerate()
+            .map(|(b, &p)| p * (b as f64 + 0.5) / 20.0)
+            .sum();
+        assert!((binned - h.mean).abs() < 0.025 + 1e-12);
+    }
+}
@@ -7,6 +7,21 @@ pub mod score;
 pub mod evaluator;
 pub mod batch;
 pub mod equity;
+pub mod abstraction;
+pub mod blockers;
+pub mod composition;
+pub mod draws;
+pub mod grid;
+pub mod hand_class;
+pub mod histogram;
+pub mod made_hand;
+pub mod nuts;
+pub mod outs;
+pub mod preflop;
+pub mod range;
+pub mod river;
+pub mod strength;
+pub mod texture;
 
 pub use card::{parse_board, parse_hand, parse_hole_cards, Card, Rank, Suit};
 pub use bitboard::{BitBoard4x13, MASK13};
@@ -14,17 +29,107 @@ pub use evaluator::{evaluate_u32, evaluate_u32_from_ids};
 pub use score::{Category, Score};
 
 pub use equity::{
+    category_odds_checked,
+    category_odds_exact_checked,
+    category_odds_mc_checked,
+    category_odds_seven_cards,
     compare_showdown_checked,
     compare_showdown_unchecked,
+    equity_exact_multiway_breakdown_checked,
     equity_exact_multiway_checked,
+    equity_exact_multiway_checked_with_dead,
+    equity_exact_multiway_pot_share_checked,
+    equity_exact_multiway_pot_share_checked_with_dead,
+    equity_exact_vs_hand_breakdown_checked,
     equity_exact_vs_hand_checked,
+    equity_exact_vs_hand_checked_with_dead,
     equity_exact_vs_random_checked,
+    equity_exact_vs_random_checked_with_dead,
+    equity_mc_adaptive,
+    equity_mc_adaptive_multiway,
+    equity_mc_crn_checked,
+    equity_mc_crn_checked_with_dead,
+    equity_mc_crn_sampled_checked,
+    equity_mc_multiway_breakdown_checked,
     equity_mc_multiway_checked,
+    equity_mc_multiway_checked_with_dead,
+    equity_mc_multiway_pot_share_checked,
+    equity_mc_multiway_pot_share_checked_with_dead,
+    equity_mc_multiway_sampled_checked,
+    equity_mc_ranges_multiway_breakdown_checked,
+    equity_mc_ranges_multiway_checked,
+    equity_mc_ranges_multiway_checked_with_dead,
+    equity_mc_ranges_multiway_pot_share_checked,
+    equity_mc_ranges_multiway_pot_share_checked_with_dead,
+    equity_mc_ranges_multiway_sampled_checked,
+    equity_mc_vs_hand_breakdown_checked,
     equity_mc_vs_hand_checked,
+    equity_mc_vs_hand_checked_with_dead,
+    equity_mc_vs_hand_sampled_checked,
+    equity_mc_vs_random_breakdown_checked,
     equity_mc_vs_random_checked,
+    equity_mc_vs_random_checked_with_dead,
     equity_mc_vs_random_multiway_checked,
+    equity_mc_vs_random_multiway_checked_with_dead,
+    equity_mc_vs_random_multiway_pot_share_checked,
+    equity_mc_vs_random_multiway_pot_share_checked_with_dead,
+    equity_mc_vs_random_multiway_sampled_checked,
+    equity_mc_vs_random_sampled_checked,
+    split_distribution,
+    AdaptiveMultiWayResult,
+    AdaptiveResult,
+    CategoryBreakdown,
+    CategoryOdds,
     EquityCounts,
     EquityError,
+    MultiWayCounts,
     MultiWayResult,
+    PairedEquity,
+    MAX_PLAYERS,
     Outcome,
+    Sampling,
+    Seat,
+    StopReason,
+    StopRule,
 };
+#[cfg(feature = "parallel")]
+pub use equity::{
+    equity_exact_multiway_checked_par,
+    equity_exact_multiway_pot_share_checked_par,
+    equity_exact_vs_hand_checked_par,
+    equity_exact_vs_random_checked_par,
+    equity_mc_multiway_checked_par,
+    equity_mc_multiway_pot_share_checked_par,
+    equity_mc_multiway_sampled_checked_par,
+    equity_mc_ranges_multiway_checked_par,
+    equity_mc_ranges_multiway_pot_share_checked_par,
+    equity_mc_ranges_multiway_sampled_checked_par,
+    equity_mc_vs_hand_checked_par,
+    equity_mc_vs_hand_sampled_checked_par,
+    equity_mc_vs_random_checked_par,
+    equity_mc_vs_random_sampled_checked_par,
+    equity_mc_vs_random_multiway_checked_par,
+    equity_mc_vs_random_multiway_pot_share_checked_par,
+    equity_mc_vs_random_multiway_sampled_checked_par,
+};
+pub use abstraction::{AbstractionConfig, BucketMap};
+pub use blockers::{blocker_report, rank_blockers, BlockerEffect, BlockerReport, ClassBlocking};
+pub use composition::{ComboClass, ComboInfo, RangeComposition};
+pub use draws::{Draws, FlushDraw, StraightDraw};
+pub use grid::{ComboEquity, EquityGrid, GridCell};
+pub use hand_class::HandClass;
+pub use histogram::{equity_histogram, equity_histograms, EquityHistogram};
+pub use made_hand::{filter_made_hands, Kicker, MadeHand, TwoPairKind};
+pub use nuts::{NutRanking, NutTier};
+pub use outs::{outs_vs_hand, outs_vs_range, CardKind, CardOutcome, OutsReport};
+pub use preflop::{
+    PreflopTable,
+    PreflopVsRandomTable,
+    MAX_RANDOM_OPPONENTS,
+    PREFLOP_RUNOUTS,
+    VS_RANDOM_EPSILON,
+};
+pub use range::{Range, WeightedCombo};
+pub use river::{river_range_equity, river_showdown, ComboShowdown};
+pub use strength::{hand_strength, hand_strength_vs_random, HandStrength};
+pub use texture::{BoardTexture, FlushTexture, HighCardClass, Pairing};
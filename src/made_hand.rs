@@ -0,0 +1,281 @@
+//! Made hands relative to the board: what hero's hole cards actually contribute.
+//!
+//! `Category` is the same for top pair and an underpair; here the hand is read
+//! against the board. Pairs are placed by the board rank they pair (top, middle,
+//! bottom) or, for pocket pairs, where they sit among the board ranks. Kickers
+//! are ranked among the ranks not on board. A hand the hole cards only add
+//! kickers to, or on the river don't improve at all, is playing the board.
+
+use std::fmt;
+
+use crate::equity::{validate_board, validate_inputs, EquityError};
+use crate::lut13::straight_end13;
+use crate::range::Range;
+use crate::score::{unpack_score, Category};
+use crate::{evaluate_u32, BitBoard4x13, MASK13};
+
+/// Kicker rank among the ranks not on board (and not the paired rank).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Kicker {
+    /// Fourth best available rank or lower.
+    Weak,
+    /// Second or third best available rank.
+    Good,
+    /// Best available rank.
+    Top,
+}
+
+/// How the two pairs of a two-pair hand come about.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TwoPairKind {
+    /// Each hole card pairs a board card.
+    BothCards,
+    /// One hole card pairs a board card; the other pair is on board.
+    OneCard,
+    /// A pocket pair on a paired board.
+    PocketPair,
+}
+
+/// Hero's hand read against the board.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MadeHand {
+    /// Nothing but high cards.
+    HighCard,
+    /// The hole cards add only kickers to the board's own hand.
+    PlayingTheBoard,
+    /// Pocket pair below every board card.
+    Underpair,
+    /// Pocket pair between the top and bottom board cards.
+    PocketPair,
+    /// Pocket pair above every board card.
+    Overpair,
+    BottomPair(Kicker),
+    /// A board rank below the top and above the bottom one.
+    MiddlePair(Kicker),
+    TopPair(Kicker),
+    TwoPair(TwoPairKind),
+    /// One hole card matching a board pair.
+    Trips(Kicker),
+    /// Pocket pair matching a board card.
+    Set,
+    /// `nut`: no two hole cards make a higher straight.
+    Straight { nut: bool },
+    /// `nut`: hero holds the highest card of the suit not on board.
+    Flush { nut: bool },
+    FullHouse,
+    Quads,
+    StraightFlush,
+}
+
+impl MadeHand {
+    /// Classify `hole` on a 3-5 card `board`.
+    pub fn new(hole: &[u8; 2], board: &[u8]) -> Result<Self, EquityError> {
+        if board.len() < 3 {
+            return Err(EquityError::TooFewBoardCards(board.len()));
+        }
+        validate_inputs(hole, None, board, &[])?;
+        let mut b = BitBoard4x13::new();
+        for &c in board {
+            b.add_id(c);
+        }
+        Ok(classify(hole, &b))
+    }
+
+    /// Hero has at least a pair that uses a hole card.
+    pub fn is_pair_or_better(self) -> bool {
+        !matches!(self, MadeHand::HighCard | MadeHand::PlayingTheBoard)
+    }
+}
+
+pub(crate) fn classify(hole: &[u8; 2], board: &BitBoard4x13) -> MadeHand {
+    let mut all = *board;
+    all.add_id(hole[0]);
+    all.add_id(hole[1]);
+    let score = evaluate_u32(&all);
+    let board_cards: u32 = board.suits_array().iter().map(|m| m.count_ones()).sum();
+    if board_cards == 5 && score == evaluate_u32(board) {
+        return MadeHand::PlayingTheBoard;
+    }
+
+    let (cat, r0, r1, ..) = unpack_score(score);
+    let (h0, h1) = (hole[0] % 13, hole[1] % 13);
+    let pocket = h0 == h1;
+    let holds = |r: u8| h0 == r || h1 == r;
+    let br = board.ranks_any();
+    let top = 15 - br.leading_zeros() as u8;
+    let bottom = br.trailing_zeros() as u8;
+
+    match cat {
+        Category::HighCard => MadeHand::HighCard,
+        Category::OnePair if pocket && h0 == r0 => {
+            if h0 > top {
+                MadeHand::Overpair
+            } else if h0 < bottom {
+                MadeHand::Underpair
+            } else {
+                MadeHand::PocketPair
+            }
+        }
+        Category::OnePair if holds(r0) => {
+            let kicker = kicker(br | (1 << r0), if h0 == r0 { h1 } else { h0 });
+            if r0 == top {
+                MadeHand::TopPair(kicker)
+            } else if r0 == bottom {
+                MadeHand::BottomPair(kicker)
+            } else {
+                MadeHand::MiddlePair(kicker)
+            }
+        }
+        Category::TwoPair if pocket && (h0 == r0 || h0 == r1) => MadeHand::TwoPair(TwoPairKind::PocketPair),
+        Category::TwoPair if holds(r0) && holds(r1) => MadeHand::TwoPair(TwoPairKind::BothCards),
+        Category::TwoPair if holds(r0) || holds(r1) => MadeHand::TwoPair(TwoPairKind::OneCard),
+        Category::Trips if pocket && h0 == r0 => MadeHand::Set,
+        Category::Trips if holds(r0) => MadeHand::Trips(kicker(br, if h0 == r0 { h1 } else { h0 })),
+        Category::Straight => {
+            let best = (0..13)
+                .flat_map(|a| (a..13).map(move |b| straight_end13(br | (1 << a) | (1 << b))))
+                .max()
+                .unwrap_or(-1);
+            MadeHand::Straight { nut: r0 as i8 == best }
+        }
+        Category::Flush => {
+            let suit = board.suits_array().iter().position(|m| m.count_ones() >= 3).expect("flush needs three board cards");
+            let open = !board.suits_array()[suit] & MASK13;
+            let top_open = 15 - open.leading_zeros() as u8;
+            let hero_top = hole.iter().filter(|&&c| (c / 13) as usize == suit).map(|c| c % 13).max();
+            MadeHand::Flush { nut: hero_top == Some(top_open) }
+        }
+        Category::FullHouse if holds(r0) || holds(r1) => MadeHand::FullHouse,
+        Category::Quads if holds(r0) => MadeHand::Quads,
+        Category::StraightFlush => MadeHand::StraightFlush,
+        _ => MadeHand::PlayingTheBoard,
+    }
+}
+
+/// Class of kicker rank `k` among the ranks outside `taken`.
+fn kicker(taken: u16, k: u8) -> Kicker {
+    let better = (k + 1..13).filter(|&r| taken & (1 << r) == 0).count();
+    match better {
+        0 => Kicker::Top,
+        1 | 2 => Kicker::Good,
+        _ => Kicker::Weak,
+    }
+}
+
+impl fmt::Display for Kicker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Kicker::Top => "top kicker",
+            Kicker::Good => "good kicker",
+            Kicker::Weak => "weak kicker",
+        })
+    }
+}
+
+impl fmt::Display for MadeHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MadeHand::HighCard => f.write_str("high card"),
+            MadeHand::PlayingTheBoard => f.write_str("playing the board"),
+            MadeHand::Underpair => f.write_str("underpair"),
+            MadeHand::PocketPair => f.write_str("pocket pair below top card"),
+            MadeHand::Overpair => f.write_str("overpair"),
+            MadeHand::BottomPair(k) => write!(f, "bottom pair, {}", k),
+            MadeHand::MiddlePair(k) => write!(f, "middle pair, {}", k),
+            MadeHand::TopPair(k) => write!(f, "top pair, {}", k),
+            MadeHand::TwoPair(TwoPairKind::BothCards) => f.write_str("two pair"),
+            MadeHand::TwoPair(TwoPairKind::OneCard) => f.write_str("two pair, one card"),
+            MadeHand::TwoPair(TwoPairKind::PocketPair) => f.write_str("pocket pair on paired board"),
+            MadeHand::Trips(k) => write!(f, "trips, {}", k),
+            MadeHand::Set => f.write_str("set"),
+            MadeHand::Straight { nut: true } => f.write_str("nut straight"),
+            MadeHand::Straight { nut: false } => f.write_str("straight"),
+            MadeHand::Flush { nut: true } => f.write_str("nut flush"),
+            MadeHand::Flush { nut: false } => f.write_str("flush"),
+            MadeHand::FullHouse => f.write_str("full house"),
+            MadeHand::Quads => f.write_str("quads"),
+            MadeHand::StraightFlush => f.write_str("straight flush"),
+        }
+    }
+}
+
+/// The combos of `range` that avoid a 3-5 card `board` and whose made hand passes
+/// `keep`, with their weights.
+pub fn filter_made_hands(range: &Range, board: &[u8], keep: impl Fn(MadeHand) -> bool) -> Result<Range, EquityError> {
+    let used = validate_board(board, &[])?;
+    let mut b = BitBoard4x13::new();
+    for &c in board {
+        b.add_id(c);
+    }
+    let mut out = Range::new();
+    for c in range.live(used).combos() {
+        if keep(classify(&c.hand, &b)) {
+            out.add(c.hand, c.weight)?;
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::ids;
+
+    fn made(hole: &str, board: &str) -> MadeHand {
+        let h = ids(hole);
+        MadeHand::new(&[h[0], h[1]], &ids(board)).unwrap()
+    }
+
+    #[test]
+    fn pairs_and_kickers() {
+        let b = "Kh 8d 3c";
+        assert_eq!(made("As Ad", b), MadeHand::Overpair);
+        assert_eq!(made("Ts Td", b), MadeHand::PocketPair);
+        assert_eq!(made("2s 2d", b), MadeHand::Underpair);
+        assert_eq!(made("Ks Ad", b), MadeHand::TopPair(Kicker::Top));
+        assert_eq!(made("Ks Jd", b), MadeHand::TopPair(Kicker::Good));
+        assert_eq!(made("Ks 9d", b), MadeHand::TopPair(Kicker::Weak));
+        assert_eq!(made("8s Ad", b), MadeHand::MiddlePair(Kicker::Top));
+        assert_eq!(made("3s 4d", b), MadeHand::BottomPair(Kicker::Weak));
+        assert_eq!(made("As Qd", b), MadeHand::HighCard);
+        // With kings on board the queen is a good kicker, behind the ace.
+        assert_eq!(made("Ks Qd", "Kh Kd 3c"), MadeHand::Trips(Kicker::Good));
+        assert_eq!(made("3s 3d", b), MadeHand::Set);
+        assert_eq!(made("Ks Qd", b).to_string(), "top pair, good kicker");
+    }
+
+    #[test]
+    fn two_pair_and_the_board() {
+        assert_eq!(made("Ks 8s", "Kh 8d 3c"), MadeHand::TwoPair(TwoPairKind::BothCards));
+        assert_eq!(made("Ks 9s", "Kh 8d 8c"), MadeHand::TwoPair(TwoPairKind::OneCard));
+        assert_eq!(made("As Ad", "Kh 8d 8c"), MadeHand::TwoPair(TwoPairKind::PocketPair));
+        assert_eq!(made("As Qd", "Kh 8d 8c"), MadeHand::PlayingTheBoard);
+        assert_eq!(made("As Qd", "7h 7d 7c"), MadeHand::PlayingTheBoard);
+        // Broadway on board, and hero can't beat it.
+        assert_eq!(made("2s 3d", "Ah Kd Qc Js Tc"), MadeHand::PlayingTheBoard);
+        // A board two pair with a better kicker still only plays the board.
+        assert_eq!(made("As 2d", "Kh Kd 8c 8s 4c"), MadeHand::PlayingTheBoard);
+        assert_eq!(made("8h 2d", "Kh Kd 8c 4s 4c"), MadeHand::TwoPair(TwoPairKind::OneCard));
+        assert!(!MadeHand::PlayingTheBoard.is_pair_or_better());
+    }
+
+    #[test]
+    fn straights_flushes_and_filtering() {
+        assert_eq!(made("Ah Th", "Kh Qd Jc"), MadeHand::Straight { nut: true });
+        assert_eq!(made("9h Th", "Kh Qd Jc"), MadeHand::Straight { nut: false });
+        assert_eq!(made("Ah 2h", "Kh 9h 3h"), MadeHand::Flush { nut: true });
+        assert_eq!(made("Qh 2h", "Kh 9h 3h"), MadeHand::Flush { nut: false });
+        // Ace on board: the king is the nut flush.
+        assert_eq!(made("Kh 2c", "Ah 9h 3h 4h"), MadeHand::Flush { nut: true });
+        assert_eq!(made("Kh Kd", "Ks 9h 9c"), MadeHand::FullHouse);
+        assert_eq!(made("9s 9d", "Ks 9h 9c"), MadeHand::Quads);
+
+        let board = ids("Kh 8d 3c");
+        let range = Range::from_hands(&[[11, 51], [12, 25], [0, 13], [37, 50]]).unwrap();
+        let pairs = filter_made_hands(&range, &board, |m| matches!(m, MadeHand::TopPair(_) | MadeHand::Overpair)).unwrap();
+        // Kh Ks touches the board and 2c 2d is an underpair.
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs.combos()[0].hand, [11, 51]);
+        assert_eq!(pairs.combos()[1].hand, [12, 25]);
+    }
+}
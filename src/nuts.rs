@@ -0,0 +1,285 @@
+//! Nut ranking: every holding on a board, strongest first.
+//!
+//! Each two-card holding that avoids the board and dead cards is scored on the
+//! board as it stands and holdings with equal scores are grouped into tiers. The
+//! first tier is the nuts, the second the second nuts. On the flop and turn every
+//! river runout is also dealt to find which holdings can still end up with the
+//! nuts, and how often.
+
+use crate::equity::{check_deck, enumerate_board_completions, fill_remaining_cards, validate_board, EquityError};
+use crate::score::Score;
+use crate::{evaluate_u32, BitBoard4x13};
+
+/// Holdings that make the same hand on the board.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NutTier {
+    pub score: Score,
+    /// Low card first, in card order.
+    pub hands: Vec<[u8; 2]>,
+}
+
+/// Every live holding on a board, grouped into tiers from the nuts down.
+#[derive(Clone, Debug)]
+pub struct NutRanking {
+    board: Vec<u8>,
+    tiers: Vec<NutTier>,
+    holdings: usize,
+    /// Tier of each holding, at `hand_slot`; `u16::MAX` where blocked.
+    tier_of: Vec<u16>,
+    /// River runouts where each holding has the nuts, at `hand_slot`.
+    nut_runouts: Vec<u32>,
+    /// River runouts that avoid any one holding.
+    runouts: u32,
+}
+
+#[inline(always)]
+fn hand_slot(hand: &[u8; 2]) -> Option<usize> {
+    let (lo, hi) = (hand[0].min(hand[1]), hand[0].max(hand[1]));
+    (hi < 52 && lo != hi).then(|| lo as usize * 52 + hi as usize)
+}
+
+impl NutRanking {
+    /// Rank every holding on a 3-5 card `board`, leaving out holdings that touch
+    /// `dead`. Dead cards are also kept out of the runouts.
+    ///
+    /// Fails with [`EquityError::NotEnoughCards`] unless a holding and a runout
+    /// can still be dealt, so the ranking is never empty.
+    pub fn new(board: &[u8], dead: &[u8]) -> Result<Self, EquityError> {
+        let used = validate_board(board, dead)?;
+        let missing = 5 - board.len();
+        check_deck(used, 2 + missing)?;
+        let mut rem = [0u8; 52];
+        let n = fill_remaining_cards(used, &mut rem);
+        let rem = &rem[..n];
+
+        let mut holdings = Vec::with_capacity(n * (n - 1) / 2);
+        for (i, &a) in rem.iter().enumerate() {
+            for &b in &rem[i + 1..] {
+                holdings.push([a, b]);
+            }
+        }
+
+        let mut bb_board = BitBoard4x13::new();
+        for &c in board {
+            bb_board.add_id(c);
+        }
+        let mut scored: Vec<(u32, [u8; 2])> = holdings
+            .iter()
+            .map(|h| {
+                let mut b = bb_board;
+                b.add_id(h[0]);
+                b.add_id(h[1]);
+                (evaluate_u32(&b).0, *h)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let mut tiers: Vec<NutTier> = Vec::new();
+        let mut tier_of = vec![u16::MAX; 52 * 52];
+        for (score, h) in scored {
+            if tiers.last().map(|t| t.score.0) != Some(score) {
+                tiers.push(NutTier { score: Score(score), hands: Vec::new() });
+            }
+            tier_of[hand_slot(&h).expect("distinct live cards")] = (tiers.len() - 1) as u16;
+            tiers.last_mut().expect("just pushed").hands.push(h);
+        }
+
+        let mut nut_runouts = vec![0u32; 52 * 52];
+        let mut runouts = 0u32;
+        if missing > 0 {
+            let mut scores = vec![0u32; holdings.len()];
+            enumerate_board_completions(rem, board, missing, |board5| {
+                let mut bb = BitBoard4x13::new();
+                let mut dealt = 0u64;
+                for &c in &board5 {
+                    bb.add_id(c);
+                    dealt |= 1u64 << c;
+                }
+                let mut best = 0u32;
+                for (s, h) in scores.iter_mut().zip(&holdings) {
+                    *s = if dealt & ((1u64 << h[0]) | (1u64 << h[1])) != 0 {
+                        0
+                    } else {
+                        let mut b = bb;
+                        b.add_id(h[0]);
+                        b.add_id(h[1]);
+                        evaluate_u32(&b).0
+                    };
+                    best = best.max(*s);
+                }
+                for (&s, h) in scores.iter().zip(&holdings) {
+                    if s == best {
+                        nut_runouts[hand_slot(h).expect("distinct live cards")] += 1;
+                    }
+                }
+            });
+            // Runouts avoiding a holding: `missing` cards from the other n - 2.
+            runouts = (0..missing).fold(1u64, |acc, i| acc * (n - 2 - i) as u64 / (i + 1) as u64) as u32;
+        }
+
+        Ok(Self {
+            board: board.to_vec(),
+            tiers,
+            holdings: holdings.len(),
+            tier_of,
+            nut_runouts,
+            runouts,
+        })
+    }
+
+    pub fn board(&self) -> &[u8] {
+        &self.board
+    }
+
+    /// Tiers from strongest to weakest.
+    pub fn tiers(&self) -> &[NutTier] {
+        &self.tiers
+    }
+
+    /// Number of holdings ranked.
+    pub fn len(&self) -> usize {
+        self.holdings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.holdings == 0
+    }
+
+    /// Holdings with the best hand on the board now.
+    pub fn nuts(&self) -> &NutTier {
+        &self.tiers[0]
+    }
+
+    pub fn second_nuts(&self) -> Option<&NutTier> {
+        self.tiers.get(1)
+    }
+
+    /// Tier of `hand` (0 for the nuts), or `None` if it touches the board or a dead card.
+    pub fn tier_of(&self, hand: &[u8; 2]) -> Option<usize> {
+        let t = *self.tier_of.get(hand_slot(hand)?)?;
+        (t != u16::MAX).then_some(t as usize)
+    }
+
+    /// Share of the other holdings `hand` beats, ties counting half: 1.0 for the
+    /// lone nuts, 0.0 for the lone worst hand.
+    pub fn percentile(&self, hand: &[u8; 2]) -> Option<f64> {
+        let t = self.tier_of(hand)?;
+        if self.holdings < 2 {
+            return Some(1.0);
+        }
+        let above: usize = self.tiers[..t].iter().map(|t| t.hands.len()).sum();
+        let ties = self.tiers[t].hands.len() - 1;
+        let below = self.holdings - 1 - above - ties;
+        Some((below as f64 + ties as f64 / 2.0) / (self.holdings - 1) as f64)
+    }
+
+    /// Share of river runouts on which `hand` holds the nuts, chops included.
+    /// On a river board this is 1.0 for the nuts and 0.0 otherwise.
+    pub fn nut_chance(&self, hand: &[u8; 2]) -> Option<f64> {
+        let t = self.tier_of(hand)?;
+        if self.runouts == 0 {
+            return Some(if t == 0 { 1.0 } else { 0.0 });
+        }
+        let k = self.nut_runouts[hand_slot(hand)?];
+        Some(k as f64 / self.runouts as f64)
+    }
+
+    /// Holdings that have the nuts on at least one river runout, strongest now first.
+    pub fn can_become_nuts(&self) -> Vec<[u8; 2]> {
+        self.tiers
+            .iter()
+            .flat_map(|t| &t.hands)
+            .filter(|h| self.nut_chance(h).is_some_and(|p| p > 0.0))
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score::Category;
+
+    #[test]
+    fn river_ranking_tiers_and_percentiles() {
+        // Ks Qs Jd 4h 2c: any ace-ten makes Broadway, then ten-nine, then top set.
+        let board = [50, 49, 22, 28, 0];
+        let r = NutRanking::new(&board, &[]).unwrap();
+        assert_eq!(r.len(), 47 * 46 / 2);
+        assert_eq!(r.tiers().iter().map(|t| t.hands.len()).sum::<usize>(), r.len());
+        assert_eq!(r.nuts().hands.len(), 16);
+        assert_eq!(r.nuts().score.category(), Category::Straight);
+        let second = r.second_nuts().unwrap();
+        assert_eq!(second.score.category(), Category::Straight);
+        assert_eq!(second.hands.len(), 16);
+        assert_eq!(r.tiers()[2].hands, vec![[11, 24], [11, 37], [24, 37]]);
+
+        assert_eq!(r.tier_of(&[47, 51]), Some(0));
+        assert_eq!(r.tier_of(&[37, 24]), Some(2));
+        let n = (r.len() - 1) as f64;
+        assert!((r.percentile(&[12, 8]).unwrap() - (n - 15.0 + 7.5) / n).abs() < 1e-12);
+        assert!((r.percentile(&[11, 24]).unwrap() - (n - 32.0 - 2.0 + 1.0) / n).abs() < 1e-12);
+        let worst = r.tiers().last().unwrap();
+        let ties = (worst.hands.len() - 1) as f64;
+        assert!((r.percentile(&worst.hands[0]).unwrap() - ties / 2.0 / n).abs() < 1e-12);
+
+        assert_eq!(r.tier_of(&[50, 1]), None);
+        assert_eq!(r.nut_chance(&[12, 8]), Some(1.0));
+        assert_eq!(r.nut_chance(&[11, 24]), Some(0.0));
+        assert_eq!(r.can_become_nuts(), r.nuts().hands);
+    }
+
+    #[test]
+    fn turn_nut_chances() {
+        // Ah Kh Qd 2c with the 5c dead: any jack-ten is the nuts now.
+        let board = [38, 37, 23, 0];
+        let dead = [3];
+        let r = NutRanking::new(&board, &dead).unwrap();
+        assert_eq!(r.len(), 47 * 46 / 2);
+        assert_eq!(r.tier_of(&[3, 4]), None);
+        assert_eq!(r.nuts().hands.len(), 16);
+        assert_eq!(r.nuts().score.category(), Category::Straight);
+
+        // Against ranking every river board on its own.
+        for hand in [[34, 35], [35, 48], [36, 33], [25, 12], [1, 14]] {
+            let mut nut = 0;
+            let mut total = 0;
+            for river in 0u8..52 {
+                if board.contains(&river) || dead.contains(&river) || hand.contains(&river) {
+                    continue;
+                }
+                let mut b5 = board.to_vec();
+                b5.push(river);
+                total += 1;
+                if NutRanking::new(&b5, &dead).unwrap().tier_of(&hand) == Some(0) {
+                    nut += 1;
+                }
+            }
+            let want = nut as f64 / total as f64;
+            assert!((r.nut_chance(&hand).unwrap() - want).abs() < 1e-12, "{:?}", hand);
+        }
+        let can = r.can_become_nuts();
+        assert!(can.contains(&[34, 35]));
+        assert!(!can.contains(&[1, 14]));
+    }
+
+    #[test]
+    fn too_few_live_cards() {
+        // River: two live cards make one holding, one live card makes none.
+        let board = [50, 49, 22, 28, 0];
+        let dead: Vec<u8> = (1u8..52).filter(|c| !board.contains(c) && *c != 2 && *c != 3).collect();
+        let r = NutRanking::new(&board, &dead).unwrap();
+        assert_eq!(r.len(), 1);
+        assert_eq!(r.nuts().hands, vec![[2, 3]]);
+        assert_eq!(r.percentile(&[2, 3]), Some(1.0));
+        assert_eq!(NutRanking::new(&board, &[&dead[..], &[2]].concat()).err(), Some(EquityError::NotEnoughCards));
+
+        // Flop: a holding plus a turn and river needs four live cards.
+        let flop = [50, 49, 22];
+        let dead: Vec<u8> = (0u8..52).filter(|c| !flop.contains(c) && *c > 3).collect();
+        let r = NutRanking::new(&flop, &dead).unwrap();
+        assert_eq!(r.len(), 6);
+        assert_eq!(r.nut_chance(&[0, 1]), Some(1.0));
+        assert_eq!(NutRanking::new(&flop, &[&dead[..], &[3]].concat()).err(), Some(EquityError::NotEnoughCards));
+    }
+}
@@ -0,0 +1,316 @@
+//! Outs: what each possible next card does to hero's hand and equity.
+//!
+//! On the flop or turn every card hero can't see is dealt in turn. For each one
+//! we report hero's equity afterwards (exact over the remaining runout), the
+//! category hero's hand reaches, and a label:
+//!
+//! - an **out** gives hero, who is not ahead now, a hand that beats what villain
+//!   holds now. It is *clean* if hero is ahead afterwards and *tainted* if the card
+//!   improves villain past hero too (a flush card that pairs the board against a set),
+//! - a **scare** card is any other card that takes the lead away from hero,
+//! - everything else is a **blank**.
+//!
+//! "Ahead" means beating more than half of villain's weight on the board as it
+//! stands (win + tie/2 > 1/2); heads-up against one hand that is simply winning.
+
+use crate::equity::{enumerate_board_completions, fill_remaining_cards, validate_inputs, EquityError};
+use crate::range::Range;
+use crate::score::{Category, Score};
+use crate::{evaluate_u32, BitBoard4x13};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CardKind {
+    Out,
+    Blank,
+    Scare,
+}
+
+/// What one card does when it falls next.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CardOutcome {
+    pub card: u8,
+    pub kind: CardKind,
+    /// Out that leaves hero behind.
+    pub tainted: bool,
+    /// Chance this card comes next; cards villain is more likely to hold are rarer.
+    pub probability: f64,
+    /// Hero's equity once the card is out.
+    pub equity: f64,
+    /// Hero's category with the card.
+    pub category: Category,
+    /// Hero's category, if it is better than hero's category before the card.
+    pub completes: Option<Category>,
+}
+
+/// Card-by-card breakdown of hero's next street.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutsReport {
+    /// Hero's equity now.
+    pub equity: f64,
+    /// Share of villain's weight hero beats now, ties counting half.
+    pub showdown_share: f64,
+    /// One entry per card that can come, in card id order.
+    pub cards: Vec<CardOutcome>,
+}
+
+impl OutsReport {
+    pub fn clean_outs(&self) -> usize {
+        self.cards.iter().filter(|c| c.kind == CardKind::Out && !c.tainted).count()
+    }
+
+    pub fn tainted_outs(&self) -> usize {
+        self.cards.iter().filter(|c| c.tainted).count()
+    }
+
+    /// Cards of one kind.
+    pub fn of_kind(&self, kind: CardKind) -> impl Iterator<Item = &CardOutcome> {
+        self.cards.iter().filter(move |c| c.kind == kind)
+    }
+}
+
+/// Outs of `hero` against a known `villain` hand on a 3 or 4 card board.
+pub fn outs_vs_hand(hero: &[u8; 2], villain: &[u8; 2], board: &[u8], dead: &[u8]) -> Result<OutsReport, EquityError> {
+    check_street(board)?;
+    let used = validate_inputs(hero, Some(villain), board, dead)?;
+    let villains = [(*villain, (1u64 << villain[0]) | (1u64 << villain[1]), 1.0)];
+    Ok(breakdown(hero, &villains, board, used))
+}
+
+/// Outs of `hero` against a weighted `villain` range on a 3 or 4 card board.
+/// Villain combos touching a known card, or the card that falls, are dropped.
+pub fn outs_vs_range(hero: &[u8; 2], villain: &Range, board: &[u8], dead: &[u8]) -> Result<OutsReport, EquityError> {
+    check_street(board)?;
+    let used = validate_inputs(hero, None, board, dead)?;
+    let villains: Vec<([u8; 2], u64, f64)> = villain
+        .live(used)
+        .combos()
+        .iter()
+        .map(|c| (c.hand, (1u64 << c.hand[0]) | (1u64 << c.hand[1]), c.weight))
+        .collect();
+    if villains.is_empty() {
+        return Err(EquityError::EmptyRange);
+    }
+    Ok(breakdown(hero, &villains, board, used))
+}
+
+pub(crate) fn check_street(board: &[u8]) -> Result<(), EquityError> {
+    match board.len() {
+        0..=2 => Err(EquityError::TooFewBoardCards(board.len())),
+        3 | 4 => Ok(()),
+        n => Err(EquityError::TooManyBoardCards(n)),
+    }
+}
+
+#[inline(always)]
+fn score_with(board: &BitBoard4x13, hand: [u8; 2]) -> u32 {
+    let mut b = *board;
+    b.add_id(hand[0]);
+    b.add_id(hand[1]);
+    evaluate_u32(&b).0
+}
+
+#[inline(always)]
+fn category(score: u32) -> Category {
+    Score(score).category()
+}
+
+/// (win, tie, lose) weight of hero against the villains avoiding `excluded`.
+fn showdown(hero: u32, bb: &BitBoard4x13, villains: &[([u8; 2], u64, f64)], excluded: u64) -> [f64; 3] {
+    let scores: Vec<u32> = villains.iter().map(|&(h, _, _)| score_with(bb, h)).collect();
+    showdown_vs(hero, villains, &scores, excluded)
+}
+
+/// [`showdown`] against given villain scores.
+fn showdown_vs(hero: u32, villains: &[([u8; 2], u64, f64)], scores: &[u32], excluded: u64) -> [f64; 3] {
+    let mut out = [0.0f64; 3];
+    for (&(_, mask, w), &v) in villains.iter().zip(scores) {
+        if mask & excluded != 0 {
+            continue;
+        }
+        let i = if hero > v {
+            0
+        } else if hero == v {
+            1
+        } else {
+            2
+        };
+        out[i] += w;
+    }
+    out
+}
+
+#[inline(always)]
+fn share(wtl: &[f64; 3]) -> f64 {
+    let t = wtl[0] + wtl[1] + wtl[2];
+    if t > 0.0 {
+        (wtl[0] + 0.5 * wtl[1]) / t
+    } else {
+        0.0
+    }
+}
+
+fn breakdown(hero: &[u8; 2], villains: &[([u8; 2], u64, f64)], board: &[u8], used: u64) -> OutsReport {
+    let mut bb_board = BitBoard4x13::new();
+    for &c in board {
+        bb_board.add_id(c);
+    }
+    let hero_now = score_with(&bb_board, *hero);
+    let villains_now: Vec<u32> = villains.iter().map(|&(h, _, _)| score_with(&bb_board, h)).collect();
+    let share_now = share(&showdown_vs(hero_now, villains, &villains_now, 0));
+    let cat_now = category(hero_now);
+
+    let mut rem = [0u8; 52];
+    let n = fill_remaining_cards(used, &mut rem);
+    let mut next = board.to_vec();
+    next.push(0);
+
+    // Per card: (pot shares, villain weight dealt) summed over its runouts.
+    let mut rows = Vec::with_capacity(n);
+    let (mut num_all, mut den_all) = (0.0f64, 0.0f64);
+    for &card in &rem[..n] {
+        *next.last_mut().unwrap() = card;
+        let mut bb = bb_board;
+        bb.add_id(card);
+        let hero_next = score_with(&bb, *hero);
+        let share_next = share(&showdown(hero_next, &bb, villains, 1u64 << card));
+        // Hero's new hand against villain's hands as they were.
+        let share_unimproved = share(&showdown_vs(hero_next, villains, &villains_now, 1u64 << card));
+
+        let (mut num, mut den) = (0.0f64, 0.0f64);
+        let mut river_rem = [0u8; 52];
+        let m = fill_remaining_cards(used | (1u64 << card), &mut river_rem);
+        enumerate_board_completions(&river_rem[..m], &next, 5 - next.len(), |board5| {
+            let mut bb5 = BitBoard4x13::new();
+            let mut dealt = 1u64 << card;
+            for (i, &c) in board5.iter().enumerate() {
+                bb5.add_id(c);
+                if i >= board.len() {
+                    dealt |= 1u64 << c;
+                }
+            }
+            let wtl = showdown(score_with(&bb5, *hero), &bb5, villains, dealt);
+            num += wtl[0] + 0.5 * wtl[1];
+            den += wtl[0] + wtl[1] + wtl[2];
+        });
+        num_all += num;
+        den_all += den;
+
+        let cat = category(hero_next);
+        let improved = cat as u8 > cat_now as u8;
+        let kind = if share_now <= 0.5 && share_unimproved > 0.5 {
+            CardKind::Out
+        } else if share_now > 0.5 && share_next <= 0.5 {
+            CardKind::Scare
+        } else {
+            CardKind::Blank
+        };
+        rows.push((
+            CardOutcome {
+                card,
+                kind,
+                tainted: kind == CardKind::Out && share_next <= 0.5,
+                probability: 0.0,
+                equity: if den > 0.0 { num / den } else { 0.0 },
+                category: cat,
+                completes: improved.then_some(cat),
+            },
+            den,
+        ));
+    }
+
+    let cards = rows
+        .into_iter()
+        .map(|(mut c, den)| {
+            c.probability = if den_all > 0.0 { den / den_all } else { 0.0 };
+            c
+        })
+        .collect();
+    OutsReport {
+        equity: if den_all > 0.0 { num_all / den_all } else { 0.0 },
+        showdown_share: share_now,
+        cards,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equity::equity_exact_vs_hand_checked;
+
+    #[test]
+    fn flush_draw_against_a_set() {
+        // Ac Qc vs 8d 8h on 8c 5c 2s, turn Kd: nine clubs, two of which (2c, Kc)
+        // pair the board and give villain a full house.
+        let hero = [12, 10];
+        let villain = [19, 32];
+        let board = [6, 3, 39, 24];
+        let r = outs_vs_hand(&hero, &villain, &board, &[]).unwrap();
+        assert_eq!(r.cards.len(), 52 - 8);
+        assert_eq!(r.showdown_share, 0.0);
+
+        let outs: Vec<u8> = r.of_kind(CardKind::Out).map(|c| c.card).collect();
+        assert_eq!(outs, vec![0, 1, 2, 4, 5, 7, 8, 9, 11]);
+        assert_eq!(r.clean_outs(), 7);
+        assert_eq!(r.tainted_outs(), 2);
+        let two = r.cards.iter().find(|c| c.card == 0).unwrap();
+        assert!(two.tainted);
+        assert_eq!(two.equity, 0.0);
+        assert_eq!(two.completes, Some(Category::Flush));
+        let ace = r.cards.iter().find(|c| c.card == 25).unwrap();
+        assert_eq!((ace.kind, ace.category, ace.completes), (CardKind::Blank, Category::OnePair, Some(Category::OnePair)));
+        // Pairing the board still lifts hero from ace high.
+        let five = r.cards.iter().find(|c| c.card == 16).unwrap();
+        assert_eq!(five.completes, Some(Category::OnePair));
+
+        let exact = equity_exact_vs_hand_checked(&hero, &villain, &board).unwrap();
+        assert!((r.equity - exact.equity()).abs() < 1e-12);
+        assert!((r.cards.iter().map(|c| c.probability).sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn flop_breakdown_matches_exact_equity() {
+        // Kh Kd vs Jh Th on Qh 9s 2c: every eight and king completes villain's straight.
+        let hero = [37, 24];
+        let villain = [35, 34];
+        let board = [36, 46, 0];
+        let r = outs_vs_hand(&hero, &villain, &board, &[]).unwrap();
+        let exact = equity_exact_vs_hand_checked(&hero, &villain, &board).unwrap();
+        assert!((r.equity - exact.equity()).abs() < 1e-12);
+        for c in &r.cards {
+            let e = equity_exact_vs_hand_checked(&hero, &villain, &[board[0], board[1], board[2], c.card]).unwrap();
+            assert!((c.equity - e.equity()).abs() < 1e-12);
+        }
+        assert_eq!(r.of_kind(CardKind::Out).count(), 0);
+        let scares: Vec<u8> = r.of_kind(CardKind::Scare).map(|c| c.card).collect();
+        assert_eq!(scares, vec![6, 11, 19, 32, 45, 50]);
+        // A king gives hero a set, but villain the straight.
+        let ks = r.cards.iter().find(|c| c.card == 50).unwrap();
+        assert_eq!(ks.completes, Some(Category::Trips));
+        assert!(ks.equity < 0.25);
+    }
+
+    #[test]
+    fn range_breakdown_and_errors() {
+        let hero = [12, 10];
+        let board = [6, 3, 39];
+        let mut villain = Range::new();
+        villain.add([19, 32], 1.0).unwrap();
+        villain.add([11, 24], 0.5).unwrap();
+        let r = outs_vs_range(&hero, &villain, &board, &[]).unwrap();
+        // Hero's own cards and the board are out of the deck; villain's are not known.
+        assert_eq!(r.cards.len(), 52 - 5);
+        assert!((r.cards.iter().map(|c| c.probability).sum::<f64>() - 1.0).abs() < 1e-12);
+        let mean: f64 = r.cards.iter().map(|c| c.probability * c.equity).sum();
+        assert!((mean - r.equity).abs() < 1e-12);
+
+        assert_eq!(outs_vs_range(&hero, &villain, &[6, 3], &[]), Err(EquityError::TooFewBoardCards(2)));
+        assert_eq!(
+            outs_vs_hand(&hero, &[19, 32], &[6, 3, 39, 24, 1], &[]),
+            Err(EquityError::TooManyBoardCards(5))
+        );
+        assert_eq!(
+            outs_vs_range(&hero, &Range::from_hands(&[[6, 7]]).unwrap(), &board, &[]),
+            Err(EquityError::EmptyRange)
+        );
+    }
+}
@@ -0,0 +1,390 @@
+//! Precomputed heads-up preflop all-in equities.
+//!
+//! Two holdings are equivalent when a suit permutation maps one matchup onto the
+//! other; there are 47,008 such matchups over the 169×169 class pairs. The table
+//! holds the exact win/tie counts of each (all 1,712,304 runouts), generated with
+//! [`equity_exact_vs_hand_checked`], and is looked up in O(1) by canonicalizing the
+//! two hands' suits.
+//!
+//! The serialized form is a 9-byte header (`b"PFEQ"`, version, entry count) followed
+//! by 3-byte little-endian win and tie counts per matchup, in canonical order.
+
+use std::sync::OnceLock;
+
+mod vs_random;
+pub use vs_random::{PreflopVsRandomTable, MAX_RANDOM_OPPONENTS, VS_RANDOM_EPSILON};
+
+use crate::equity::{equity_exact_vs_hand_checked, EquityCounts, EquityError};
+use crate::hand_class::HandClass;
+
+/// Runouts of a heads-up preflop all-in: C(48, 5).
+pub const PREFLOP_RUNOUTS: u64 = 1_712_304;
+
+const MAGIC: &[u8; 4] = b"PFEQ";
+const VERSION: u8 = 1;
+const HEADER: usize = 9;
+const ENTRY: usize = 6;
+
+/// Suit patterns of four cards as restricted growth strings (first appearance
+/// order), indexed by the base-4 code of the relabeled suits; 15 are valid.
+const fn build_patterns() -> [u8; 256] {
+    let mut t = [u8::MAX; 256];
+    let mut id = 0u8;
+    let mut code = 0usize;
+    while code < 256 {
+        let d = [code >> 6, (code >> 4) & 3, (code >> 2) & 3, code & 3];
+        let mut ok = d[0] == 0;
+        let mut max = 0;
+        let mut i = 1;
+        while i < 4 {
+            if d[i] > max + 1 {
+                ok = false;
+            }
+            if d[i] > max {
+                max = d[i];
+            }
+            i += 1;
+        }
+        if ok {
+            t[code] = id;
+            id += 1;
+        }
+        code += 1;
+    }
+    t
+}
+
+const PATTERN: [u8; 256] = build_patterns();
+
+/// The cards of a holding, higher rank first; pairs can be in either order.
+#[inline(always)]
+fn orders(h: [u8; 2]) -> ([[u8; 2]; 2], usize) {
+    let (a, b) = if h[0] % 13 >= h[1] % 13 { (h[0], h[1]) } else { (h[1], h[0]) };
+    if a % 13 == b % 13 {
+        ([[a, b], [b, a]], 2)
+    } else {
+        ([[a, b], [a, b]], 1)
+    }
+}
+
+/// (class pair, suit pattern) of `hero` vs `villain`, invariant under suit permutations.
+#[inline]
+fn oriented_key(hero: [u8; 2], villain: [u8; 2]) -> (u32, u8) {
+    let pair = (HandClass::from_hand(hero).index() * HandClass::COUNT
+        + HandClass::from_hand(villain).index()) as u32;
+    let (ho, hn) = orders(hero);
+    let (vo, vn) = orders(villain);
+    let mut best = usize::MAX;
+    for h in &ho[..hn] {
+        for v in &vo[..vn] {
+            let mut map = [u8::MAX; 4];
+            let mut next = 0u8;
+            let mut code = 0usize;
+            for c in [h[0], h[1], v[0], v[1]] {
+                let s = (c / 13) as usize;
+                if map[s] == u8::MAX {
+                    map[s] = next;
+                    next += 1;
+                }
+                code = code * 4 + map[s] as usize;
+            }
+            best = best.min(code);
+        }
+    }
+    (pair, PATTERN[best])
+}
+
+/// Stored key of a matchup and whether it is stored from villain's side.
+#[inline]
+fn canonical_key(hero: [u8; 2], villain: [u8; 2]) -> ((u32, u8), bool) {
+    let a = oriented_key(hero, villain);
+    let b = oriented_key(villain, hero);
+    if a <= b {
+        (a, false)
+    } else {
+        (b, true)
+    }
+}
+
+/// Where each canonical matchup lives in the entry list. Depends only on the
+/// card encoding, so it is built once and shared.
+struct Layout {
+    /// Per class pair: bit `p` set if suit pattern `p` is stored for it.
+    masks: Vec<u16>,
+    /// Per class pair: index of its first entry.
+    offsets: Vec<u32>,
+    /// One concrete matchup per entry, in stored orientation.
+    reps: Vec<([u8; 2], [u8; 2])>,
+    /// Unordered holding pairs mapping to each entry.
+    mult: Vec<u32>,
+}
+
+impl Layout {
+    #[inline(always)]
+    fn index(&self, key: (u32, u8)) -> usize {
+        let (pair, pat) = (key.0 as usize, key.1);
+        self.offsets[pair] as usize + (self.masks[pair] & ((1u16 << pat) - 1)).count_ones() as usize
+    }
+
+    fn build() -> Self {
+        let hands: Vec<[u8; 2]> = (0u8..52)
+            .flat_map(|a| ((a + 1)..52).map(move |b| [a, b]))
+            .collect();
+        let pairs = HandClass::COUNT * HandClass::COUNT;
+
+        let mut masks = vec![0u16; pairs];
+        let for_each_matchup = |f: &mut dyn FnMut([u8; 2], [u8; 2])| {
+            for (i, &h) in hands.iter().enumerate() {
+                for &v in &hands[i + 1..] {
+                    if h[0] != v[0] && h[0] != v[1] && h[1] != v[0] && h[1] != v[1] {
+                        f(h, v);
+                    }
+                }
+            }
+        };
+        for_each_matchup(&mut |h, v| {
+            let (key, _) = canonical_key(h, v);
+            masks[key.0 as usize] |= 1 << key.1;
+        });
+
+        let mut offsets = vec![0u32; pairs];
+        let mut n = 0u32;
+        for (o, m) in offsets.iter_mut().zip(&masks) {
+            *o = n;
+            n += m.count_ones();
+        }
+
+        let mut layout = Self {
+            masks,
+            offsets,
+            reps: vec![([0, 0], [0, 0]); n as usize],
+            mult: vec![0; n as usize],
+        };
+        for_each_matchup(&mut |h, v| {
+            let (key, swapped) = canonical_key(h, v);
+            let i = layout.index(key);
+            if layout.mult[i] == 0 {
+                layout.reps[i] = if swapped { (v, h) } else { (h, v) };
+            }
+            layout.mult[i] += 1;
+        });
+        layout
+    }
+
+    fn get() -> &'static Layout {
+        static LAYOUT: OnceLock<Layout> = OnceLock::new();
+        LAYOUT.get_or_init(Layout::build)
+    }
+}
+
+/// Exact heads-up preflop equity of every matchup, with O(1) lookup.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreflopTable {
+    /// (win, tie) per canonical matchup, from the stored side.
+    entries: Vec<[u32; 2]>,
+    /// Hero equity per class pair, averaged over all non-overlapping holdings.
+    class_equity: Vec<f64>,
+}
+
+impl PreflopTable {
+    /// Compute every matchup with the exact enumerator (a few minutes single-threaded).
+    pub fn generate() -> Self {
+        let layout = Layout::get();
+        let entries = layout.reps.iter().map(|(h, v)| exact_entry(h, v)).collect();
+        Self::from_entries(entries)
+    }
+
+    /// [`generate`](Self::generate) across threads; identical output.
+    #[cfg(feature = "parallel")]
+    pub fn generate_par() -> Self {
+        use rayon::prelude::*;
+        let layout = Layout::get();
+        let entries = layout.reps.par_iter().map(|(h, v)| exact_entry(h, v)).collect();
+        Self::from_entries(entries)
+    }
+
+    /// The table shipped with the crate.
+    pub fn embedded() -> &'static PreflopTable {
+        static TABLE: OnceLock<PreflopTable> = OnceLock::new();
+        TABLE.get_or_init(|| {
+            Self::from_bytes(include_bytes!("../data/preflop_hu.bin")).expect("embedded preflop table is valid")
+        })
+    }
+
+    fn from_entries(entries: Vec<[u32; 2]>) -> Self {
+        let layout = Layout::get();
+        let n = HandClass::COUNT;
+        let mut sums = vec![0u64; n * n];
+        let mut pots = vec![0u64; n * n];
+        for (pair, (&mask, &offset)) in layout.masks.iter().zip(&layout.offsets).enumerate() {
+            let (a, b) = (pair / n, pair % n);
+            for k in 0..mask.count_ones() as usize {
+                let i = offset as usize + k;
+                let [win, tie] = entries[i];
+                let lose = PREFLOP_RUNOUTS as u32 - win - tie;
+                let m = layout.mult[i] as u64;
+                // Doubled pot shares (2 * win + tie) keep the sums integral.
+                sums[a * n + b] += m * (2 * win as u64 + tie as u64);
+                sums[b * n + a] += m * (2 * lose as u64 + tie as u64);
+                pots[a * n + b] += m;
+                pots[b * n + a] += m;
+            }
+        }
+        let class_equity = sums
+            .iter()
+            .zip(&pots)
+            .map(|(&s, &m)| {
+                if m == 0 {
+                    0.0
+                } else {
+                    s as f64 / (2 * m * PREFLOP_RUNOUTS) as f64
+                }
+            })
+            .collect();
+        Self { entries, class_equity }
+    }
+
+    /// Number of canonical matchups.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Serialize to the compact format described in the module docs.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER + ENTRY * self.entries.len());
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for &[win, tie] in &self.entries {
+            out.extend_from_slice(&win.to_le_bytes()[..3]);
+            out.extend_from_slice(&tie.to_le_bytes()[..3]);
+        }
+        out
+    }
+
+    /// Load a table written by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EquityError> {
+        let layout = Layout::get();
+        let n = layout.reps.len();
+        if bytes.len() != HEADER + ENTRY * n
+            || &bytes[..4] != MAGIC
+            || bytes[4] != VERSION
+            || u32::from_le_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]) as usize != n
+        {
+            return Err(EquityError::InvalidTable);
+        }
+        let mut entries = Vec::with_capacity(n);
+        for e in bytes[HEADER..].chunks_exact(ENTRY) {
+            let win = u32::from_le_bytes([e[0], e[1], e[2], 0]);
+            let tie = u32::from_le_bytes([e[3], e[4], e[5], 0]);
+            if win as u64 + tie as u64 > PREFLOP_RUNOUTS {
+                return Err(EquityError::InvalidTable);
+            }
+            entries.push([win, tie]);
+        }
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Exact counts of `hero` vs `villain` over all runouts.
+    pub fn lookup(&self, hero: &[u8; 2], villain: &[u8; 2]) -> Result<EquityCounts, EquityError> {
+        let mut used = 0u64;
+        for &c in hero.iter().chain(villain) {
+            if c >= 52 {
+                return Err(EquityError::CardOutOfRange(c));
+            }
+            if (used >> c) & 1 != 0 {
+                return Err(EquityError::DuplicateCard(c));
+            }
+            used |= 1u64 << c;
+        }
+        let (key, swapped) = canonical_key(*hero, *villain);
+        let [win, tie] = self.entries[Layout::get().index(key)];
+        let lose = PREFLOP_RUNOUTS - win as u64 - tie as u64;
+        let (win, lose) = if swapped { (lose, win as u64) } else { (win as u64, lose) };
+        Ok(EquityCounts {
+            win,
+            tie: tie as u64,
+            lose,
+        })
+    }
+
+    /// Hero's all-in equity against `villain`.
+    pub fn equity(&self, hero: &[u8; 2], villain: &[u8; 2]) -> Result<f64, EquityError> {
+        self.lookup(hero, villain).map(|c| c.equity())
+    }
+
+    /// Equity of class `hero` against class `villain`, averaged over every pair of
+    /// non-overlapping holdings.
+    #[inline(always)]
+    pub fn class_equity(&self, hero: HandClass, villain: HandClass) -> f64 {
+        self.class_equity[hero.index() * HandClass::COUNT + villain.index()]
+    }
+}
+
+fn exact_entry(hero: &[u8; 2], villain: &[u8; 2]) -> [u32; 2] {
+    let c = equity_exact_vs_hand_checked(hero, villain, &[]).expect("representative hands are disjoint");
+    [c.win as u32, c.tie as u32]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_counts_isomorphic_matchups() {
+        let layout = Layout::get();
+        assert_eq!(layout.reps.len(), 47_008);
+        // Every unordered pair of disjoint holdings is counted once.
+        assert_eq!(layout.mult.iter().map(|&m| m as u64).sum::<u64>(), 1326 * 1225 / 2);
+    }
+
+    #[test]
+    fn canonical_key_ignores_suit_permutations() {
+        // AsKs vs QhJh and AdKd vs QcJc are the same matchup.
+        assert_eq!(canonical_key([51, 50], [36, 35]), canonical_key([25, 24], [10, 9]));
+        // ...but not the same as AsKs vs QsJh.
+        assert_ne!(canonical_key([51, 50], [36, 35]), canonical_key([51, 50], [49, 35]));
+        let (k1, s1) = canonical_key([51, 50], [36, 35]);
+        let (k2, s2) = canonical_key([36, 35], [51, 50]);
+        assert_eq!(k1, k2);
+        assert_ne!(s1, s2);
+    }
+
+    #[test]
+    fn embedded_table_matches_exact_enumeration() {
+        let table = PreflopTable::embedded();
+        for (hero, villain) in [
+            ([12u8, 25], [11u8, 24]),
+            ([11, 24], [12, 25]),
+            ([51, 50], [36, 35]),
+            ([0, 13], [1, 40]),
+            ([51, 38], [12, 0]),
+        ] {
+            let exact = equity_exact_vs_hand_checked(&hero, &villain, &[]).unwrap();
+            assert_eq!(table.lookup(&hero, &villain).unwrap(), exact);
+        }
+        assert_eq!(table.lookup(&[0, 1], &[1, 2]), Err(EquityError::DuplicateCard(1)));
+    }
+
+    #[test]
+    fn class_equity_and_roundtrip() {
+        let table = PreflopTable::embedded();
+        let aa: HandClass = "AA".parse().unwrap();
+        let kk: HandClass = "KK".parse().unwrap();
+        let e = table.class_equity(aa, kk);
+        assert!((e - 0.82).abs() < 0.01);
+        assert!((e + table.class_equity(kk, aa) - 1.0).abs() < 1e-12);
+        assert!((table.class_equity(aa, aa) - 0.5).abs() < 1e-12);
+
+        let bytes = table.to_bytes();
+        assert_eq!(bytes.len(), 9 + 6 * 47_008);
+        assert_eq!(&PreflopTable::from_bytes(&bytes).unwrap(), table);
+        assert_eq!(PreflopTable::from_bytes(&bytes[1..]), Err(EquityError::InvalidTable));
+    }
+}
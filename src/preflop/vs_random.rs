@@ -0,0 +1,243 @@
+//! Preflop equity of each starting-hand class against 1..=8 random opponents.
+//!
+//! Every holding of a class has the same equity against random hands, so one
+//! representative per class is enough. Heads-up values are exact, summed from
+//! [`PreflopTable`] over all 1,225 villain holdings; against two or more
+//! opponents they come from [`equity_mc_vs_random_multiway_pot_share_checked`], run until
+//! the 95% interval is within [`VS_RANDOM_EPSILON`].
+//!
+//! The serialized form is a 6-byte header (`b"PFRN"`, version, opponent count)
+//! followed by little-endian f64 equity and standard error per (class, opponents),
+//! class-major in chart order.
+
+use std::sync::OnceLock;
+
+use super::{PreflopTable, PREFLOP_RUNOUTS};
+use crate::equity::{
+    equity_mc_adaptive_multiway, equity_mc_vs_random_multiway_pot_share_checked, EquityError, StopRule,
+};
+use crate::hand_class::HandClass;
+
+/// Largest opponent count in the table.
+pub const MAX_RANDOM_OPPONENTS: usize = 8;
+
+/// Half-width of the 95% interval the Monte Carlo entries are run to.
+pub const VS_RANDOM_EPSILON: f64 = 0.001;
+
+const MAGIC: &[u8; 4] = b"PFRN";
+const VERSION: u8 = 1;
+const HEADER: usize = 6;
+const ENTRY: usize = 16;
+
+/// Equity of every class against 1..=[`MAX_RANDOM_OPPONENTS`] random hands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreflopVsRandomTable {
+    /// (equity, standard error) per class and opponent count; the error is 0
+    /// for exact entries.
+    entries: Vec<[f64; 2]>,
+}
+
+impl PreflopVsRandomTable {
+    /// Compute the table: heads-up from the embedded [`PreflopTable`], the rest
+    /// by Monte Carlo (several minutes single-threaded).
+    pub fn generate() -> Self {
+        let entries = (0..HandClass::COUNT * MAX_RANDOM_OPPONENTS).map(compute_entry).collect();
+        Self { entries }
+    }
+
+    /// [`generate`](Self::generate) across threads; identical output.
+    #[cfg(feature = "parallel")]
+    pub fn generate_par() -> Self {
+        use rayon::prelude::*;
+        let entries = (0..HandClass::COUNT * MAX_RANDOM_OPPONENTS)
+            .into_par_iter()
+            .map(compute_entry)
+            .collect();
+        Self { entries }
+    }
+
+    /// The table shipped with the crate.
+    pub fn embedded() -> &'static PreflopVsRandomTable {
+        static TABLE: OnceLock<PreflopVsRandomTable> = OnceLock::new();
+        TABLE.get_or_init(|| {
+            Self::from_bytes(include_bytes!("../../data/preflop_vs_random.bin"))
+                .expect("embedded preflop vs random table is valid")
+        })
+    }
+
+    /// Serialize to the format described in the module docs.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER + ENTRY * self.entries.len());
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.push(MAX_RANDOM_OPPONENTS as u8);
+        for &[e, se] in &self.entries {
+            out.extend_from_slice(&e.to_le_bytes());
+            out.extend_from_slice(&se.to_le_bytes());
+        }
+        out
+    }
+
+    /// Load a table written by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EquityError> {
+        let n = HandClass::COUNT * MAX_RANDOM_OPPONENTS;
+        if bytes.len() != HEADER + ENTRY * n
+            || &bytes[..4] != MAGIC
+            || bytes[4] != VERSION
+            || bytes[5] as usize != MAX_RANDOM_OPPONENTS
+        {
+            return Err(EquityError::InvalidTable);
+        }
+        let mut entries = Vec::with_capacity(n);
+        for e in bytes[HEADER..].chunks_exact(ENTRY) {
+            let eq = f64::from_le_bytes(e[..8].try_into().unwrap());
+            let se = f64::from_le_bytes(e[8..].try_into().unwrap());
+            if !((0.0..=1.0).contains(&eq) && se >= 0.0 && se.is_finite()) {
+                return Err(EquityError::InvalidTable);
+            }
+            entries.push([eq, se]);
+        }
+        Ok(Self { entries })
+    }
+
+    #[inline]
+    fn entry(&self, class: HandClass, opponents: usize) -> Result<[f64; 2], EquityError> {
+        check_opponents(opponents)?;
+        Ok(self.entries[class.index() * MAX_RANDOM_OPPONENTS + opponents - 1])
+    }
+
+    /// All-in equity of `class` against `opponents` random hands.
+    pub fn equity(&self, class: HandClass, opponents: usize) -> Result<f64, EquityError> {
+        self.entry(class, opponents).map(|e| e[0])
+    }
+
+    /// Standard error of [`equity`](Self::equity); 0 where the value is exact.
+    pub fn std_error(&self, class: HandClass, opponents: usize) -> Result<f64, EquityError> {
+        self.entry(class, opponents).map(|e| e[1])
+    }
+
+    /// Equity of a concrete holding (card ids 0..51).
+    pub fn hand_equity(&self, hand: &[u8; 2], opponents: usize) -> Result<f64, EquityError> {
+        for &c in hand {
+            if c >= 52 {
+                return Err(EquityError::CardOutOfRange(c));
+            }
+        }
+        if hand[0] == hand[1] {
+            return Err(EquityError::DuplicateCard(hand[0]));
+        }
+        self.equity(HandClass::from_hand(*hand), opponents)
+    }
+
+    /// All classes from best to worst against `opponents` random hands
+    /// (equal equities in chart order).
+    pub fn ranking(&self, opponents: usize) -> Result<Vec<HandClass>, EquityError> {
+        check_opponents(opponents)?;
+        let eq = |c: &HandClass| self.entries[c.index() * MAX_RANDOM_OPPONENTS + opponents - 1][0];
+        let mut classes: Vec<HandClass> = HandClass::all().collect();
+        classes.sort_by(|a, b| eq(b).total_cmp(&eq(a)).then(a.cmp(b)));
+        Ok(classes)
+    }
+
+    /// Share of all 1,326 holdings in classes ranked at or above `class`, so AA
+    /// is 6/1326 and the worst class is 1.
+    pub fn percentile(&self, class: HandClass, opponents: usize) -> Result<f64, EquityError> {
+        let mut combos = 0;
+        for c in self.ranking(opponents)? {
+            combos += c.num_combos();
+            if c == class {
+                break;
+            }
+        }
+        Ok(combos as f64 / 1326.0)
+    }
+}
+
+fn check_opponents(opponents: usize) -> Result<(), EquityError> {
+    if opponents < 1 {
+        return Err(EquityError::TooFewPlayers);
+    }
+    if opponents > MAX_RANDOM_OPPONENTS {
+        return Err(EquityError::TooManyPlayers);
+    }
+    Ok(())
+}
+
+/// Entry `i` = class `i / 8` against `i % 8 + 1` opponents.
+fn compute_entry(i: usize) -> [f64; 2] {
+    let class = HandClass::from_index(i / MAX_RANDOM_OPPONENTS).unwrap();
+    let opponents = i % MAX_RANDOM_OPPONENTS + 1;
+    let hero = class.combos()[0];
+    if opponents == 1 {
+        return [exact_heads_up(hero), 0.0];
+    }
+    let res = equity_mc_adaptive_multiway(&StopRule::new(VS_RANDOM_EPSILON), i as u64, |iters, seed| {
+        equity_mc_vs_random_multiway_pot_share_checked(&hero, opponents, &[], iters, seed).map(|c| vec![c])
+    })
+    .expect("representative hand is valid");
+    let hero = &res.results[0];
+    [hero.equity(), hero.std_error()]
+}
+
+/// Exact heads-up equity against a random hand: every villain holding is
+/// equally likely and plays the same number of runouts.
+fn exact_heads_up(hero: [u8; 2]) -> f64 {
+    let table = PreflopTable::embedded();
+    let used = (1u64 << hero[0]) | (1u64 << hero[1]);
+    let mut shares = 0u64;
+    let mut holdings = 0u64;
+    for a in 0u8..52 {
+        for b in (a + 1)..52 {
+            if used & ((1u64 << a) | (1u64 << b)) != 0 {
+                continue;
+            }
+            let c = table.lookup(&hero, &[a, b]).expect("disjoint holdings");
+            shares += 2 * c.win + c.tie;
+            holdings += 1;
+        }
+    }
+    shares as f64 / (2 * holdings * PREFLOP_RUNOUTS) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heads_up_column_is_exact() {
+        let table = PreflopVsRandomTable::embedded();
+        for name in ["AA", "72o", "T9s"] {
+            let class: HandClass = name.parse().unwrap();
+            let hero = class.combos()[0];
+            let exact = exact_heads_up(hero);
+            assert_eq!(table.equity(class, 1).unwrap(), exact);
+            assert_eq!(table.std_error(class, 1).unwrap(), 0.0);
+            // Every holding of the class has the same equity.
+            assert_eq!(exact_heads_up(class.combos()[3]), exact);
+        }
+    }
+
+    #[test]
+    fn lookup_ranking_and_roundtrip() {
+        let table = PreflopVsRandomTable::embedded();
+        let aa: HandClass = "AA".parse().unwrap();
+        for n in 1..=MAX_RANDOM_OPPONENTS {
+            assert_eq!(table.ranking(n).unwrap()[0], aa);
+            assert!(table.std_error(aa, n).unwrap() <= VS_RANDOM_EPSILON / 1.9);
+        }
+        // AA: ~85% heads-up, ~35% against eight.
+        assert!((table.equity(aa, 1).unwrap() - 0.852).abs() < 0.002);
+        assert!((table.equity(aa, 8).unwrap() - 0.35).abs() < 0.02);
+        assert_eq!(table.hand_equity(&[51, 38], 3).unwrap(), table.equity(aa, 3).unwrap());
+        assert!((table.percentile(aa, 1).unwrap() - 6.0 / 1326.0).abs() < 1e-12);
+        let worst = *table.ranking(1).unwrap().last().unwrap();
+        assert_eq!(table.percentile(worst, 1).unwrap(), 1.0);
+        assert_eq!(table.equity(aa, 0), Err(EquityError::TooFewPlayers));
+        assert_eq!(table.equity(aa, 9), Err(EquityError::TooManyPlayers));
+
+        let bytes = table.to_bytes();
+        assert_eq!(bytes.len(), 6 + 16 * 169 * 8);
+        assert_eq!(&PreflopVsRandomTable::from_bytes(&bytes).unwrap(), table);
+        assert_eq!(PreflopVsRandomTable::from_bytes(&bytes[..100]), Err(EquityError::InvalidTable));
+    }
+}
@@ -0,0 +1,171 @@
+//! Weighted hand ranges.
+
+use crate::equity::EquityError;
+
+/// A two-card holding with a relative weight (e.g. the frequency it is played).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WeightedCombo {
+    pub hand: [u8; 2],
+    pub weight: f64,
+}
+
+/// A weighted set of two-card holdings. Combos are stored with the lower card id first.
+#[derive(Clone, Debug, Default)]
+pub struct Range {
+    combos: Vec<WeightedCombo>,
+    /// Position + 1 of each combo at `hand_slot`, 0 where absent; empty until first needed.
+    slots: Vec<u16>,
+}
+
+/// Ranges are equal when they hold the same combos in the same order.
+impl PartialEq for Range {
+    fn eq(&self, other: &Self) -> bool {
+        self.combos == other.combos
+    }
+}
+
+#[inline(always)]
+fn hand_slot(hand: &[u8; 2]) -> usize {
+    hand[0] as usize * 52 + hand[1] as usize
+}
+
+impl Range {
+    pub const fn new() -> Self {
+        Self {
+            combos: Vec::new(),
+            slots: Vec::new(),
+        }
+    }
+
+    fn from_combos(combos: Vec<WeightedCombo>) -> Self {
+        let mut slots = vec![0u16; 52 * 52];
+        for (i, c) in combos.iter().enumerate() {
+            slots[hand_slot(&c.hand)] = i as u16 + 1;
+        }
+        Self { combos, slots }
+    }
+
+    /// All 1326 holdings with weight 1.
+    pub fn random() -> Self {
+        let mut combos = Vec::with_capacity(1326);
+        for a in 0u8..51 {
+            for b in (a + 1)..52 {
+                combos.push(WeightedCombo {
+                    hand: [a, b],
+                    weight: 1.0,
+                });
+            }
+        }
+        Self::from_combos(combos)
+    }
+
+    /// Equal-weight range from a list of hands.
+    pub fn from_hands(hands: &[[u8; 2]]) -> Result<Self, EquityError> {
+        let mut r = Self::new();
+        for h in hands {
+            r.add(*h, 1.0)?;
+        }
+        Ok(r)
+    }
+
+    /// Add a holding, or overwrite its weight if already present.
+    /// A weight of zero keeps the combo but it is never dealt.
+    pub fn add(&mut self, hand: [u8; 2], weight: f64) -> Result<(), EquityError> {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(EquityError::InvalidWeight);
+        }
+        for &c in &hand {
+            if c >= 52 {
+                return Err(EquityError::CardOutOfRange(c));
+            }
+        }
+        if hand[0] == hand[1] {
+            return Err(EquityError::DuplicateCard(hand[0]));
+        }
+        let hand = [hand[0].min(hand[1]), hand[0].max(hand[1])];
+        if self.slots.is_empty() {
+            self.slots = vec![0u16; 52 * 52];
+        }
+        match self.slots[hand_slot(&hand)] {
+            0 => {
+                self.combos.push(WeightedCombo { hand, weight });
+                self.slots[hand_slot(&hand)] = self.combos.len() as u16;
+            }
+            i => self.combos[i as usize - 1].weight = weight,
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn combos(&self) -> &[WeightedCombo] {
+        &self.combos
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    pub fn total_weight(&self) -> f64 {
+        self.combos.iter().map(|c| c.weight).sum()
+    }
+
+    /// Combos with positive weight that avoid every card in `used` (a card mask).
+    pub(crate) fn live(&self, used: u64) -> Self {
+        let combos = self
+            .combos
+            .iter()
+            .filter(|c| c.weight > 0.0 && used & ((1u64 << c.hand[0]) | (1u64 << c.hand[1])) == 0)
+            .copied()
+            .collect();
+        Self::from_combos(combos)
+    }
+
+    /// Weight of a holding (0 if absent). Card order does not matter.
+    pub fn weight_of(&self, hand: [u8; 2]) -> f64 {
+        let hand = [hand[0].min(hand[1]), hand[0].max(hand[1])];
+        if hand[1] >= 52 {
+            return 0.0;
+        }
+        match self.slots.get(hand_slot(&hand)) {
+            Some(&i) if i > 0 => self.combos[i as usize - 1].weight,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_has_all_combos() {
+        let r = Range::random();
+        assert_eq!(r.len(), 1326);
+        assert_eq!(r.total_weight(), 1326.0);
+    }
+
+    #[test]
+    fn add_normalizes_and_overwrites() {
+        let mut r = Range::new();
+        r.add([14, 3], 0.5).unwrap();
+        r.add([3, 14], 0.25).unwrap();
+        assert_eq!(r.len(), 1);
+        assert_eq!(r.combos()[0].hand, [3, 14]);
+        assert_eq!(r.weight_of([14, 3]), 0.25);
+    }
+
+    #[test]
+    fn add_rejects_bad_input() {
+        let mut r = Range::new();
+        assert_eq!(r.add([0, 0], 1.0), Err(EquityError::DuplicateCard(0)));
+        assert_eq!(r.add([0, 52], 1.0), Err(EquityError::CardOutOfRange(52)));
+        assert_eq!(r.add([0, 1], -1.0), Err(EquityError::InvalidWeight));
+        assert_eq!(r.add([0, 1], f64::NAN), Err(EquityError::InvalidWeight));
+    }
+}
@@ -0,0 +1,261 @@
+//! River showdowns between two weighted ranges.
+//!
+//! Both ranges are scored once on the complete board and sorted by score. A sweep
+//! through villain's combos keeps the weight below the current hero score, in
+//! total and per card; a hero combo then beats `below - below[a] - below[b]` of
+//! villain's weight, the per-card terms removing the combos it blocks. That is
+//! O(n log n) for the whole range instead of O(n²) pairwise comparisons.
+
+use crate::equity::EquityError;
+use crate::range::Range;
+use crate::{evaluate_u32, BitBoard4x13};
+
+/// Villain weight a hero combo beats, ties and loses to on the river.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ComboShowdown {
+    pub hand: [u8; 2],
+    pub win: f64,
+    pub tie: f64,
+    pub lose: f64,
+}
+
+impl ComboShowdown {
+    /// Villain weight not blocked by this combo (0 if it touches the board).
+    #[inline(always)]
+    pub fn total(&self) -> f64 {
+        self.win + self.tie + self.lose
+    }
+
+    /// Share of the pot against villain's range: (win + tie/2) / total.
+    pub fn equity(&self) -> f64 {
+        let t = self.total();
+        if t <= 0.0 {
+            return 0.0;
+        }
+        (self.win + 0.5 * self.tie) / t
+    }
+}
+
+/// Weight and per-card weight of the villain combos swept so far.
+struct Sweep {
+    total: f64,
+    card: [f64; 52],
+}
+
+impl Sweep {
+    fn new() -> Self {
+        Self {
+            total: 0.0,
+            card: [0.0; 52],
+        }
+    }
+
+    #[inline(always)]
+    fn add(&mut self, hand: [u8; 2], w: f64) {
+        self.total += w;
+        self.card[hand[0] as usize] += w;
+        self.card[hand[1] as usize] += w;
+    }
+
+    /// Swept weight not sharing a card with `hand`, counting `hand` itself as
+    /// `own` (it was subtracted once per card).
+    #[inline(always)]
+    fn unblocked(&self, hand: [u8; 2], own: f64) -> f64 {
+        self.total - self.card[hand[0] as usize] - self.card[hand[1] as usize] + own
+    }
+}
+
+/// Score the combos of `range` that avoid `board_mask` and have positive weight.
+fn scored_combos(range: &Range, bb_board: &BitBoard4x13, board_mask: u64) -> Vec<(u32, [u8; 2], f64)> {
+    let mut out: Vec<(u32, [u8; 2], f64)> = range
+        .combos()
+        .iter()
+        .filter(|c| c.weight > 0.0 && (board_mask & ((1u64 << c.hand[0]) | (1u64 << c.hand[1]))) == 0)
+        .map(|c| {
+            let mut b = *bb_board;
+            b.add_id(c.hand[0]);
+            b.add_id(c.hand[1]);
+            (evaluate_u32(&b).0, c.hand, c.weight)
+        })
+        .collect();
+    out.sort_unstable_by_key(|&(s, _, _)| s);
+    out
+}
+
+/// Showdown of every hero combo against villain's range on a complete board.
+///
+/// Returns one entry per combo of `hero`, in the range's order; combos touching the
+/// board get all-zero weights. Villain combos are weighted by their range weight and
+/// removed where they share a card with the hero combo.
+pub fn river_showdown(hero: &Range, villain: &Range, board: &[u8; 5]) -> Result<Vec<ComboShowdown>, EquityError> {
+    let mut board_mask = 0u64;
+    let mut bb_board = BitBoard4x13::new();
+    for &c in board {
+        if c >= 52 {
+            return Err(EquityError::CardOutOfRange(c));
+        }
+        if (board_mask >> c) & 1 != 0 {
+            return Err(EquityError::DuplicateCard(c));
+        }
+        board_mask |= 1u64 << c;
+        bb_board.add_id(c);
+    }
+
+    let vill = scored_combos(villain, &bb_board, board_mask);
+    let mut all = Sweep::new();
+    // Villain weight of each exact holding, to add back the combo blocked by both cards.
+    let mut own = vec![0.0f64; 52 * 52];
+    for &(_, h, w) in &vill {
+        all.add(h, w);
+        own[h[0] as usize * 52 + h[1] as usize] = w;
+    }
+
+    let mut out: Vec<ComboShowdown> = hero
+        .combos()
+        .iter()
+        .map(|c| ComboShowdown {
+            hand: c.hand,
+            ..Default::default()
+        })
+        .collect();
+
+    // Hero combos by score, keeping their index in the range.
+    let mut order: Vec<(u32, usize)> = hero
+        .combos()
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| (board_mask & ((1u64 << c.hand[0]) | (1u64 << c.hand[1]))) == 0)
+        .map(|(i, c)| {
+            let mut b = bb_board;
+            b.add_id(c.hand[0]);
+            b.add_id(c.hand[1]);
+            (evaluate_u32(&b).0, i)
+        })
+        .collect();
+    order.sort_unstable();
+
+    let mut below = Sweep::new();
+    let mut below_eq = Sweep::new();
+    let (mut lt, mut le) = (0usize, 0usize);
+    for &(score, i) in &order {
+        while lt < vill.len() && vill[lt].0 < score {
+            below.add(vill[lt].1, vill[lt].2);
+            lt += 1;
+        }
+        while le < vill.len() && vill[le].0 <= score {
+            below_eq.add(vill[le].1, vill[le].2);
+            le += 1;
+        }
+
+        let h = out[i].hand;
+        // An identical villain combo scores the same, so it only appears in `below_eq`.
+        let w = own[h[0] as usize * 52 + h[1] as usize];
+        let win = below.unblocked(h, 0.0);
+        let win_tie = below_eq.unblocked(h, w);
+        let total = all.unblocked(h, w);
+        // Clamp float noise from the subtractions.
+        out[i].win = win.max(0.0);
+        out[i].tie = (win_tie - win).max(0.0);
+        out[i].lose = (total - win_tie).max(0.0);
+    }
+    Ok(out)
+}
+
+/// Hero range's equity against villain's range on a complete board: each hero
+/// combo's equity weighted by its range weight times the villain weight it faces.
+pub fn river_range_equity(hero: &Range, villain: &Range, board: &[u8; 5]) -> Result<f64, EquityError> {
+    let rows = river_showdown(hero, villain, board)?;
+    let (mut num, mut den) = (0.0, 0.0);
+    for (row, c) in rows.iter().zip(hero.combos()) {
+        num += c.weight * (row.win + 0.5 * row.tie);
+        den += c.weight * row.total();
+    }
+    if den <= 0.0 {
+        return Err(EquityError::EmptyRange);
+    }
+    Ok(num / den)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equity::{compare_showdown_checked, Outcome};
+
+    /// Deterministic pseudo-random weighted range.
+    fn test_range(seed: u64, keep: u64) -> Range {
+        let mut r = Range::new();
+        let mut x = seed;
+        for a in 0u8..52 {
+            for b in (a + 1)..52 {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                if x % 100 < keep {
+                    r.add([a, b], (x >> 40) as f64 / (1u64 << 24) as f64).unwrap();
+                }
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn sweep_matches_pairwise() {
+        let board = [0, 14, 28, 42, 7];
+        let hero = test_range(3, 30);
+        let villain = test_range(11, 40);
+        let rows = river_showdown(&hero, &villain, &board).unwrap();
+        assert_eq!(rows.len(), hero.len());
+
+        let board_mask: u64 = board.iter().map(|&c| 1u64 << c).sum();
+        for (row, hc) in rows.iter().zip(hero.combos()) {
+            let h = hc.hand;
+            let (mut win, mut tie, mut lose) = (0.0, 0.0, 0.0);
+            let hm = (1u64 << h[0]) | (1u64 << h[1]);
+            if hm & board_mask == 0 {
+                for vc in villain.combos() {
+                    let v = vc.hand;
+                    let vm = (1u64 << v[0]) | (1u64 << v[1]);
+                    if vm & (board_mask | hm) != 0 {
+                        continue;
+                    }
+                    match compare_showdown_checked(&h, &v, &board).unwrap() {
+                        Outcome::HeroWin => win += vc.weight,
+                        Outcome::Tie => tie += vc.weight,
+                        Outcome::VillainWin => lose += vc.weight,
+                    }
+                }
+            }
+            assert_eq!(row.hand, h);
+            assert!((row.win - win).abs() < 1e-9, "{:?} win {} vs {}", h, row.win, win);
+            assert!((row.tie - tie).abs() < 1e-9, "{:?} tie {} vs {}", h, row.tie, tie);
+            assert!((row.lose - lose).abs() < 1e-9, "{:?} lose {} vs {}", h, row.lose, lose);
+        }
+    }
+
+    #[test]
+    fn identical_ranges_are_even() {
+        // Symmetric matchup: the range's equity against itself is exactly 1/2.
+        let board = [51, 37, 23, 9, 0];
+        let r = test_range(5, 50);
+        let e = river_range_equity(&r, &r, &board).unwrap();
+        assert!((e - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn board_combos_and_errors() {
+        let board = [0, 1, 2, 3, 4];
+        let hero = Range::from_hands(&[[0, 20], [30, 31]]).unwrap();
+        let villain = Range::from_hands(&[[40, 41]]).unwrap();
+        let rows = river_showdown(&hero, &villain, &board).unwrap();
+        assert_eq!(rows[0].total(), 0.0);
+        assert_eq!(rows[1].total(), 1.0);
+        assert_eq!(
+            river_showdown(&hero, &villain, &[0, 0, 2, 3, 4]),
+            Err(EquityError::DuplicateCard(0))
+        );
+        assert_eq!(
+            river_range_equity(&Range::from_hands(&[[0, 9]]).unwrap(), &villain, &board),
+            Err(EquityError::EmptyRange)
+        );
+    }
+}
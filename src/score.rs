@@ -19,6 +19,14 @@ pub enum Category {
     StraightFlush = 8,
 }
 
+impl Score {
+    /// Category of the hand this score belongs to.
+    #[inline(always)]
+    pub fn category(self) -> Category {
+        unpack_score(self).0
+    }
+}
+
 #[inline(always)]
 pub fn pack_score(cat: Category, r0: u8, r1: u8, r2: u8, r3: u8, r4: u8) -> Score {
     Score(
@@ -0,0 +1,247 @@
+//! Hand strength metrics: HS, PPot/NPot, EHS and EHS².
+//!
+//! Hero's hand is compared with every holding of the opponent range on the
+//! current board (HS), then on every completion of the board to the river. The
+//! (now, river) outcome pairs give the potentials:
+//!
+//! - `ppot`: chance of ending ahead when currently behind (ties count half),
+//! - `npot`: chance of ending behind when currently ahead,
+//! - `ehs = hs * (1 - npot) + (1 - hs) * ppot`,
+//! - `ehs2`: mean over runouts of the squared river hand strength, each runout
+//!   weighted by the opponent weight it leaves live.
+//!
+//! Runouts are dealt from the cards hero can't see, each opponent holding weighted by
+//! its range weight and skipped on runouts it collides with.
+
+use crate::equity::{enumerate_board_completions, fill_remaining_cards, validate_inputs, EquityError};
+use crate::range::Range;
+use crate::{evaluate_u32, BitBoard4x13};
+
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+/// Strength metrics of one hand against a range; all in [0,1].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct HandStrength {
+    /// Share of the opponent range hero beats now, ties counting half.
+    pub hs: f64,
+    /// Chance to end ahead when behind now.
+    pub ppot: f64,
+    /// Chance to end behind when ahead now.
+    pub npot: f64,
+    /// Effective hand strength.
+    pub ehs: f64,
+    /// Expected squared hand strength on the river.
+    pub ehs2: f64,
+}
+
+#[inline(always)]
+fn outcome(hero: u32, villain: u32) -> usize {
+    if hero > villain {
+        AHEAD
+    } else if hero == villain {
+        TIED
+    } else {
+        BEHIND
+    }
+}
+
+/// Strength of `hero` on a 3-5 card `board` against the combos of `villain`.
+///
+/// Villain combos touching hero's cards, the board or `dead` are dropped. On the
+/// river both potentials are 0 and `ehs2 = hs²`.
+pub fn hand_strength(hero: &[u8; 2], villain: &Range, board: &[u8], dead: &[u8]) -> Result<HandStrength, EquityError> {
+    if board.len() < 3 {
+        return Err(EquityError::TooFewBoardCards(board.len()));
+    }
+    let used = validate_inputs(hero, None, board, dead)?;
+
+    let mut bb_board = BitBoard4x13::new();
+    for &c in board {
+        bb_board.add_id(c);
+    }
+    let score = |b: &BitBoard4x13, h: [u8; 2]| {
+        let mut b = *b;
+        b.add_id(h[0]);
+        b.add_id(h[1]);
+        evaluate_u32(&b).0
+    };
+
+    // (hand, card mask, weight, outcome now) of every live villain combo.
+    let hero_now = score(&bb_board, *hero);
+    let mut now = [0.0f64; 3];
+    let villains: Vec<([u8; 2], u64, f64, usize)> = villain
+        .combos()
+        .iter()
+        .filter_map(|c| {
+            let mask = (1u64 << c.hand[0]) | (1u64 << c.hand[1]);
+            if c.weight <= 0.0 || mask & used != 0 {
+                return None;
+            }
+            let o = outcome(hero_now, score(&bb_board, c.hand));
+            now[o] += c.weight;
+            Some((c.hand, mask, c.weight, o))
+        })
+        .collect();
+    let total: f64 = now.iter().sum();
+    if total <= 0.0 {
+        return Err(EquityError::EmptyRange);
+    }
+    let hs = (now[AHEAD] + 0.5 * now[TIED]) / total;
+
+    let mut rem = [0u8; 52];
+    let n = fill_remaining_cards(used, &mut rem);
+    // hp[now][river], weighted by villain weight per (combo, runout).
+    let mut hp = [[0.0f64; 3]; 3];
+    let (mut sq_sum, mut live) = (0.0f64, 0.0f64);
+    enumerate_board_completions(&rem[..n], board, 5 - board.len(), |board5| {
+        let mut bb = BitBoard4x13::new();
+        let mut runout = 0u64;
+        for (i, &c) in board5.iter().enumerate() {
+            bb.add_id(c);
+            if i >= board.len() {
+                runout |= 1u64 << c;
+            }
+        }
+        let hero_river = score(&bb, *hero);
+        let mut river = [0.0f64; 3];
+        for &(h, mask, w, o) in &villains {
+            if mask & runout != 0 {
+                continue;
+            }
+            let r = outcome(hero_river, score(&bb, h));
+            hp[o][r] += w;
+            river[r] += w;
+        }
+        let t: f64 = river.iter().sum();
+        if t > 0.0 {
+            let s = (river[AHEAD] + 0.5 * river[TIED]) / t;
+            sq_sum += t * s * s;
+            live += t;
+        }
+    });
+
+    let row = |i: usize| hp[i].iter().sum::<f64>();
+    let ratio = |num: f64, den: f64| if den > 0.0 { num / den } else { 0.0 };
+    let ppot = ratio(
+        hp[BEHIND][AHEAD] + 0.5 * hp[BEHIND][TIED] + 0.5 * hp[TIED][AHEAD],
+        row(BEHIND) + 0.5 * row(TIED),
+    );
+    let npot = ratio(
+        hp[AHEAD][BEHIND] + 0.5 * hp[TIED][BEHIND] + 0.5 * hp[AHEAD][TIED],
+        row(AHEAD) + 0.5 * row(TIED),
+    );
+    Ok(HandStrength {
+        hs,
+        ppot,
+        npot,
+        ehs: hs * (1.0 - npot) + (1.0 - hs) * ppot,
+        ehs2: ratio(sq_sum, live),
+    })
+}
+
+/// [`hand_strength`] against a uniformly random opponent hand.
+pub fn hand_strength_vs_random(hero: &[u8; 2], board: &[u8], dead: &[u8]) -> Result<HandStrength, EquityError> {
+    hand_strength(hero, &Range::random(), board, dead)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::river::river_range_equity;
+
+    fn score_of(cards: &[u8]) -> u32 {
+        let mut b = BitBoard4x13::new();
+        for &c in cards {
+            b.add_id(c);
+        }
+        evaluate_u32(&b).0
+    }
+
+    #[test]
+    fn river_metrics_match_showdown() {
+        let hero = [12, 11]; // Ac Kc
+        let board = [10, 9, 23, 36, 51];
+        let st = hand_strength_vs_random(&hero, &board, &[]).unwrap();
+        let eq = river_range_equity(&Range::from_hands(&[hero]).unwrap(), &Range::random(), &board).unwrap();
+        assert!((st.hs - eq).abs() < 1e-12);
+        assert_eq!((st.ppot, st.npot), (0.0, 0.0));
+        assert!((st.ehs - st.hs).abs() < 1e-12);
+        assert!((st.ehs2 - st.hs * st.hs).abs() < 1e-12);
+    }
+
+    #[test]
+    fn potentials_match_villain_outer_enumeration() {
+        // Flush draw vs a small weighted range, against a direct count with runouts
+        // dealt around each villain holding.
+        let hero = [12, 7]; // Ac 9c
+        let board = [3, 5, 48];
+        let mut villain = Range::new();
+        for (h, w) in [([11u8, 24u8], 1.0), ([22, 35], 0.5), ([1, 14], 2.0), ([4, 17], 1.0), ([15, 40], 0.25)] {
+            villain.add(h, w).unwrap();
+        }
+        let st = hand_strength(&hero, &villain, &board, &[]).unwrap();
+
+        let mut hp = [[0.0f64; 3]; 3];
+        // Live villain weight per outcome on each (turn, river) runout.
+        let mut rivers = vec![[0.0f64; 3]; 52 * 52];
+        let base: u64 = [hero[0], hero[1], board[0], board[1], board[2]].iter().map(|&c| 1u64 << c).sum();
+        for c in villain.combos() {
+            let v = c.hand;
+            let now = outcome(
+                score_of(&[hero[0], hero[1], board[0], board[1], board[2]]),
+                score_of(&[v[0], v[1], board[0], board[1], board[2]]),
+            );
+            let used = base | (1u64 << v[0]) | (1u64 << v[1]);
+            for t in 0u8..52 {
+                for r in (t + 1)..52 {
+                    if used & ((1u64 << t) | (1u64 << r)) != 0 {
+                        continue;
+                    }
+                    let b = [board[0], board[1], board[2], t, r];
+                    let hs = score_of(&[hero[0], hero[1], b[0], b[1], b[2], b[3], b[4]]);
+                    let vs = score_of(&[v[0], v[1], b[0], b[1], b[2], b[3], b[4]]);
+                    hp[now][outcome(hs, vs)] += c.weight;
+                    rivers[t as usize * 52 + r as usize][outcome(hs, vs)] += c.weight;
+                }
+            }
+        }
+        let row = |i: usize| hp[i].iter().sum::<f64>();
+        let ppot = (hp[BEHIND][AHEAD] + 0.5 * hp[BEHIND][TIED] + 0.5 * hp[TIED][AHEAD])
+            / (row(BEHIND) + 0.5 * row(TIED));
+        let npot =
+            (hp[AHEAD][BEHIND] + 0.5 * hp[TIED][BEHIND] + 0.5 * hp[AHEAD][TIED]) / (row(AHEAD) + 0.5 * row(TIED));
+        assert!((st.ppot - ppot).abs() < 1e-12, "{} vs {}", st.ppot, ppot);
+        assert!((st.npot - npot).abs() < 1e-12, "{} vs {}", st.npot, npot);
+        assert!(st.ppot > 0.0 && st.npot > 0.0);
+
+        let (mut sq_sum, mut live) = (0.0, 0.0);
+        for river in &rivers {
+            let t: f64 = river.iter().sum();
+            if t > 0.0 {
+                let s = (river[AHEAD] + 0.5 * river[TIED]) / t;
+                sq_sum += t * s * s;
+                live += t;
+            }
+        }
+        assert!((st.ehs2 - sq_sum / live).abs() < 1e-12, "{} vs {}", st.ehs2, sq_sum / live);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            hand_strength_vs_random(&[0, 1], &[2, 3], &[]),
+            Err(EquityError::TooFewBoardCards(2))
+        );
+        assert_eq!(
+            hand_strength_vs_random(&[0, 1], &[1, 3, 4], &[]),
+            Err(EquityError::DuplicateCard(1))
+        );
+        let villain = Range::from_hands(&[[2, 3]]).unwrap();
+        assert_eq!(
+            hand_strength(&[0, 1], &villain, &[2, 5, 6], &[]),
+            Err(EquityError::EmptyRange)
+        );
+    }
+}
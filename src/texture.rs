@@ -0,0 +1,326 @@
+//! Board texture: suits, pairing, straight possibilities and height of a 3-5
+//! card board.
+//!
+//! Straight predicates come from the board's rank mask and [`STRAIGHT_END13`]:
+//! every pair of hole ranks is added to the board and checked for a straight, and
+//! on the flop and turn for how many ranks would complete one. Hole cards are
+//! counted by rank only, so "one rank pair makes a straight" covers all of its
+//! suit combinations.
+
+use crate::bitboard::BitBoard4x13;
+use crate::card::{Rank, Suit};
+use crate::equity::{validate_board, EquityError};
+use crate::lut13::{popcnt13, STRAIGHT_END13};
+
+/// Suit distribution, by the largest number of cards of one suit.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum FlushTexture {
+    /// No two cards share a suit.
+    Rainbow,
+    /// Two cards of a suit at most: no flush yet, but a draw on the flop or turn.
+    TwoTone,
+    /// Three of a suit: two hole cards of the suit make a flush.
+    ThreeFlush,
+    /// Four of a suit: a single card of the suit makes a flush.
+    FourFlush,
+    /// Five of a suit: a flush on board.
+    FiveFlush,
+}
+
+/// Paired ranks on the board.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Pairing {
+    Unpaired,
+    Paired,
+    DoublePaired,
+    Trips,
+    /// Trips and a pair: a full house on board.
+    FullHouse,
+    Quads,
+}
+
+/// Class of the board's highest card.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum HighCardClass {
+    /// Five high or lower.
+    Low,
+    /// Six to nine high.
+    Middle,
+    /// Ten to king high.
+    Broadway,
+    Ace,
+}
+
+impl HighCardClass {
+    pub fn of(rank: Rank) -> Self {
+        match rank.idx() {
+            12 => HighCardClass::Ace,
+            8..=11 => HighCardClass::Broadway,
+            4..=7 => HighCardClass::Middle,
+            _ => HighCardClass::Low,
+        }
+    }
+}
+
+/// Texture of a 3-5 card board.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BoardTexture {
+    /// Board cards.
+    pub cards: u8,
+    /// Board cards per suit, indexed by `Suit::idx`.
+    pub suit_counts: [u8; 4],
+    pub flush: FlushTexture,
+    pub pairing: Pairing,
+    /// Highest board rank.
+    pub high: Rank,
+    pub high_class: HighCardClass,
+    /// Board ranks, bit `Rank::idx`.
+    pub ranks: u16,
+    /// Most distinct board ranks inside one five-rank straight window (wheel included).
+    pub straight_window: u8,
+    /// Straights someone can hold, as a mask of their top ranks (bit 3 is the wheel).
+    pub straights: u16,
+    /// Hole rank pairs (of the 91, pocket pairs included) that make a straight.
+    pub straight_rank_pairs: u8,
+    /// Hole rank pairs without a straight that have two or more completing ranks
+    /// (open-ended draws and double gutshots); flop and turn only.
+    pub open_ended_rank_pairs: u8,
+    /// Hole rank pairs without a straight that have exactly one completing rank;
+    /// flop and turn only.
+    pub gutshot_rank_pairs: u8,
+}
+
+#[inline(always)]
+fn straight_end(mask: u16) -> i8 {
+    STRAIGHT_END13[mask as usize]
+}
+
+/// Ranks whose addition to `mask` makes a straight.
+#[inline]
+fn completing_ranks(mask: u16) -> u8 {
+    (0..13).filter(|r| mask & (1 << r) == 0 && straight_end(mask | (1 << r)) >= 0).count() as u8
+}
+
+impl BoardTexture {
+    /// Classify a 3-5 card `board`.
+    pub fn new(board: &[u8]) -> Result<Self, EquityError> {
+        validate_board(board, &[])?;
+        let mut bb = BitBoard4x13::new();
+        for &c in board {
+            bb.add_id(c);
+        }
+
+        let suit_counts = bb.suits_array().map(popcnt13);
+        let flush = match suit_counts.iter().max().copied().unwrap_or(0) {
+            0 | 1 => FlushTexture::Rainbow,
+            2 => FlushTexture::TwoTone,
+            3 => FlushTexture::ThreeFlush,
+            4 => FlushTexture::FourFlush,
+            _ => FlushTexture::FiveFlush,
+        };
+
+        let ranks = bb.ranks_any();
+        let (ge2, ge3, ge4) = (bb.ge2(), bb.ge3(), bb.ge4());
+        let pairing = if ge4 != 0 {
+            Pairing::Quads
+        } else if ge3 != 0 && ge2 != ge3 {
+            Pairing::FullHouse
+        } else if ge3 != 0 {
+            Pairing::Trips
+        } else if popcnt13(ge2) >= 2 {
+            Pairing::DoublePaired
+        } else if ge2 != 0 {
+            Pairing::Paired
+        } else {
+            Pairing::Unpaired
+        };
+
+        let high = Rank::ALL[15 - ranks.leading_zeros() as usize];
+
+        // Windows ending at five (the wheel, with the ace) up to the ace.
+        let straight_window = (3..13)
+            .map(|end| {
+                let window = if end == 3 { 0x100F } else { 0x1F << (end - 4) };
+                popcnt13(ranks & window)
+            })
+            .max()
+            .unwrap_or(0);
+
+        let mut straights = 0u16;
+        let mut straight_rank_pairs = 0u8;
+        let mut open_ended_rank_pairs = 0u8;
+        let mut gutshot_rank_pairs = 0u8;
+        for r1 in 0..13 {
+            for r2 in r1..13 {
+                let m = ranks | (1 << r1) | (1 << r2);
+                let end = straight_end(m);
+                if end >= 0 {
+                    straights |= 1 << end;
+                    straight_rank_pairs += 1;
+                } else if board.len() < 5 {
+                    match completing_ranks(m) {
+                        0 => {}
+                        1 => gutshot_rank_pairs += 1,
+                        _ => open_ended_rank_pairs += 1,
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            cards: board.len() as u8,
+            suit_counts,
+            flush,
+            pairing,
+            high,
+            high_class: HighCardClass::of(high),
+            ranks,
+            straight_window,
+            straights,
+            straight_rank_pairs,
+            open_ended_rank_pairs,
+            gutshot_rank_pairs,
+        })
+    }
+
+    /// Every card a different suit.
+    pub fn is_rainbow(&self) -> bool {
+        self.flush == FlushTexture::Rainbow
+    }
+
+    /// Some suit twice, none three times.
+    pub fn is_two_tone(&self) -> bool {
+        self.flush == FlushTexture::TwoTone
+    }
+
+    /// Every card the same suit.
+    pub fn is_monotone(&self) -> bool {
+        self.suit_counts.contains(&self.cards)
+    }
+
+    /// Someone can hold a flush.
+    pub fn flush_possible(&self) -> bool {
+        self.flush >= FlushTexture::ThreeFlush
+    }
+
+    /// Five of a suit on board.
+    pub fn flush_complete(&self) -> bool {
+        self.flush == FlushTexture::FiveFlush
+    }
+
+    /// Suit with three or more cards, if any.
+    pub fn flush_suit(&self) -> Option<Suit> {
+        let s = self.suit_counts.iter().position(|&n| n >= 3)?;
+        Some(Suit::ALL[s])
+    }
+
+    /// At least one rank appears twice.
+    pub fn is_paired(&self) -> bool {
+        self.pairing != Pairing::Unpaired
+    }
+
+    /// Someone can hold a straight.
+    pub fn straight_possible(&self) -> bool {
+        self.straights != 0
+    }
+
+    /// A straight on board.
+    pub fn straight_complete(&self) -> bool {
+        straight_end(self.ranks) >= 0
+    }
+
+    /// A single hole card can make a straight.
+    pub fn one_card_straight(&self) -> bool {
+        self.straight_window >= 4
+    }
+
+    /// Highest straight someone can hold, by its top rank.
+    pub fn best_straight(&self) -> Option<Rank> {
+        (self.straights != 0).then(|| Rank::ALL[15 - self.straights.leading_zeros() as usize])
+    }
+
+    /// Board ranks of ten or higher.
+    pub fn broadway_cards(&self) -> u8 {
+        popcnt13(self.ranks & 0x1F00)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::ids;
+
+    fn texture(s: &str) -> BoardTexture {
+        BoardTexture::new(&ids(s)).unwrap()
+    }
+
+    #[test]
+    fn flush_and_pairing() {
+        let t = texture("Ks 7d 2c");
+        assert!(t.is_rainbow() && !t.is_paired() && !t.flush_possible());
+        assert_eq!(t.high_class, HighCardClass::Broadway);
+        assert_eq!(t.high, Rank::King);
+
+        let t = texture("9h 8h 2h");
+        assert!(t.is_monotone() && t.flush_possible() && !t.flush_complete());
+        assert_eq!(t.flush, FlushTexture::ThreeFlush);
+        assert_eq!(t.flush_suit(), Some(Suit::Hearts));
+
+        let t = texture("Ah Ad 7h 7s");
+        assert!(t.is_two_tone() && !t.is_monotone());
+        assert_eq!(t.pairing, Pairing::DoublePaired);
+        assert_eq!(t.high_class, HighCardClass::Ace);
+
+        assert_eq!(texture("5c 5d 5h").pairing, Pairing::Trips);
+        assert_eq!(texture("5c 5d 5h Kc Kd").pairing, Pairing::FullHouse);
+        assert_eq!(texture("5c 5d 5h 5s").pairing, Pairing::Quads);
+        assert!(texture("2s 5s 9s Js Ks").flush_complete());
+        assert_eq!(texture("4c 3d 2h").high_class, HighCardClass::Low);
+
+        assert_eq!(BoardTexture::new(&[0, 1]), Err(EquityError::TooFewBoardCards(2)));
+        assert_eq!(BoardTexture::new(&[0, 1, 1]), Err(EquityError::DuplicateCard(1)));
+    }
+
+    #[test]
+    fn straight_possibilities() {
+        // K 7 2: two hole cards reach three ranks of a window at most.
+        let t = texture("Ks 7d 2c");
+        assert_eq!(t.straight_window, 1);
+        assert!(!t.straight_possible());
+        assert_eq!(t.best_straight(), None);
+        assert_eq!(t.straight_rank_pairs, 0);
+        assert_eq!(t.open_ended_rank_pairs, 0);
+        assert_eq!(t.gutshot_rank_pairs, 0);
+
+        // K Q 2: AJ, AT and JT make gutshots or better.
+        let t = texture("Ks Qd 2c");
+        assert_eq!(t.straight_window, 2);
+        assert!(t.open_ended_rank_pairs > 0 && t.gutshot_rank_pairs > 0);
+
+        // 9 8 7: JT, T6 and 65; best straight jack high.
+        let t = texture("9h 8d 7c");
+        assert_eq!(t.straight_window, 3);
+        assert!(t.straight_possible() && !t.one_card_straight());
+        assert_eq!(t.best_straight(), Some(Rank::Jack));
+        assert_eq!(t.straights, (1 << 9) | (1 << 8) | (1 << 7));
+        assert_eq!(t.straight_rank_pairs, 3);
+
+        // A 2 3: only 54 makes a straight, the wheel.
+        let t = texture("Ac 2d 3h");
+        assert_eq!(t.straight_window, 3);
+        assert_eq!(t.best_straight(), Some(Rank::Five));
+        assert_eq!(t.straight_rank_pairs, 1);
+
+        // 9 T J Q: any K or 8 completes it; one card is enough.
+        let t = texture("9h Td Jc Qs");
+        assert!(t.one_card_straight());
+        assert_eq!(t.best_straight(), Some(Rank::Ace));
+        assert!(!t.straight_complete());
+
+        let t = texture("9h Td Jc Qs Ks");
+        assert!(t.straight_complete());
+        assert_eq!(t.straight_rank_pairs, 91);
+        assert_eq!(t.open_ended_rank_pairs + t.gutshot_rank_pairs, 0);
+        assert_eq!(t.broadway_cards(), 4);
+    }
+}